@@ -0,0 +1,207 @@
+use std::collections::{BTreeSet, HashMap};
+
+// Keeps the elves split into the `top_k` heaviest and the rest, so that updating a snack is
+// O(log n) and the top-k sum is always at hand.
+#[derive(Debug)]
+pub struct Leaderboard
+{
+    top_k: usize,
+    elves: Vec<Elf>,
+    top: BTreeSet<(i32, usize)>,
+    rest: BTreeSet<(i32, usize)>,
+    top_sum: i32,
+}
+
+#[derive(Debug,Default)]
+struct Elf
+{
+    snacks: HashMap<i32, usize>,
+    total: i32,
+}
+
+impl Leaderboard
+{
+    pub fn new(top_k: usize) -> Leaderboard
+    {
+        Leaderboard
+        {
+            top_k,
+            elves: Vec::new(),
+            top: BTreeSet::new(),
+            rest: BTreeSet::new(),
+            top_sum: 0,
+        }
+    }
+
+    pub fn from_calory_lists(calory_lists: &[Vec<i32>], top_k: usize) -> Leaderboard
+    {
+        let mut leaderboard = Leaderboard::new(top_k);
+        for (elf, list) in calory_lists.iter().enumerate()
+        {
+            leaderboard.add_elf(elf);
+            for calories in list
+            {
+                leaderboard.add_snack(elf, *calories);
+            }
+        }
+
+        leaderboard
+    }
+
+    pub fn get_top_sum(&self) -> i32 { self.top_sum }
+
+    pub fn get_total(&self, elf: usize) -> Option<i32>
+    {
+        self.elves.get(elf).map(|elf| elf.total)
+    }
+
+    pub fn num_elves(&self) -> usize { self.elves.len() }
+
+    // Makes sure the elf exists, even if it doesn't carry anything yet.
+    pub fn add_elf(&mut self, elf: usize)
+    {
+        while self.elves.len() <= elf
+        {
+            let new_elf = self.elves.len();
+            self.elves.push(Elf::default());
+            self.insert((0, new_elf));
+        }
+    }
+
+    pub fn add_snack(&mut self, elf: usize, calories: i32)
+    {
+        self.add_elf(elf);
+        self.elves[elf].snacks.entry(calories)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+        self.change_total(elf, calories);
+    }
+
+    pub fn remove_snack(&mut self, elf: usize, calories: i32) -> Result<(), anyhow::Error>
+    {
+        let snacks = self.elves.get_mut(elf)
+            .map(|elf| &mut elf.snacks)
+            .ok_or_else(|| anyhow::Error::msg(format!("elf {elf} doesn't exist!")))?;
+
+        match snacks.get_mut(&calories)
+        {
+            Some(count) if *count > 1 => *count -= 1,
+            Some(_) => { snacks.remove(&calories); },
+            None => return Err(anyhow::Error::msg(format!("elf {elf} doesn't carry a snack with {calories} calories!"))),
+        }
+
+        self.change_total(elf, -calories);
+        Ok(())
+    }
+
+    fn change_total(&mut self, elf: usize, delta: i32)
+    {
+        let entry = (self.elves[elf].total, elf);
+        if self.top.remove(&entry)
+        {
+            self.top_sum -= entry.0;
+        }
+        else
+        {
+            self.rest.remove(&entry);
+        }
+
+        self.elves[elf].total += delta;
+        self.insert((self.elves[elf].total, elf));
+    }
+
+    fn insert(&mut self, entry: (i32, usize))
+    {
+        self.rest.insert(entry);
+
+        while self.top.len() < self.top_k
+        {
+            match self.rest.pop_last()
+            {
+                Some(promoted) => self.promote(promoted),
+                None => break,
+            }
+        }
+
+        while let (Some(lowest_top), Some(highest_rest)) = (self.top.first().copied(), self.rest.last().copied())
+        {
+            if lowest_top >= highest_rest
+            {
+                break;
+            }
+
+            self.top.remove(&lowest_top);
+            self.top_sum -= lowest_top.0;
+            self.rest.remove(&highest_rest);
+            self.rest.insert(lowest_top);
+            self.promote(highest_rest);
+        }
+    }
+
+    fn promote(&mut self, entry: (i32, usize))
+    {
+        self.top_sum += entry.0;
+        self.top.insert(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::sum_of_top_calories;
+
+    #[test]
+    fn matches_batch()
+    {
+        let mut calory_lists = vec![
+            vec![1000, 2000, 3000],
+            vec![4000],
+            vec![5000, 6000],
+            vec![7000, 8000, 9000],
+            vec![10000],
+        ];
+
+        let mut leaderboard = Leaderboard::from_calory_lists(&calory_lists, 3);
+        assert_eq!(leaderboard.get_top_sum(), 45000);
+
+        let mut seed = 12345u32;
+        let mut next = |bound: u32| { seed = seed.wrapping_mul(1103515245).wrapping_add(12345); (seed >> 16) % bound };
+
+        for _ in 0..500
+        {
+            let elf = next(8) as usize;
+            let remove = next(3) == 0 && calory_lists.get(elf).is_some_and(|list| !list.is_empty());
+
+            if remove
+            {
+                let index = next(calory_lists[elf].len() as u32) as usize;
+                let calories = calory_lists[elf].swap_remove(index);
+                leaderboard.remove_snack(elf, calories).unwrap();
+            }
+            else
+            {
+                let calories = next(10000) as i32;
+                if calory_lists.len() <= elf
+                {
+                    calory_lists.resize(elf + 1, Vec::new());
+                }
+                calory_lists[elf].push(calories);
+                leaderboard.add_snack(elf, calories);
+            }
+
+            assert_eq!(leaderboard.get_top_sum(), sum_of_top_calories(&calory_lists, 3));
+        }
+    }
+
+    #[test]
+    fn remove_missing_snack()
+    {
+        let mut leaderboard = Leaderboard::from_calory_lists(&[vec![1000]], 3);
+        assert!(leaderboard.remove_snack(0, 2000).is_err());
+        assert!(leaderboard.remove_snack(1, 1000).is_err());
+        assert!(leaderboard.remove_snack(0, 1000).is_ok());
+        assert_eq!(leaderboard.get_top_sum(), 0);
+        assert_eq!(leaderboard.get_total(0), Some(0));
+    }
+}
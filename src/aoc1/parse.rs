@@ -0,0 +1,81 @@
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum BlankLines
+{
+    // Any run of blank lines separates two elves.
+    Collapse,
+    // Every blank line after the first one in a run is an elf carrying nothing.
+    EmptyElves,
+}
+
+// Lines containing only whitespace count as blank. Blank lines at the start and the end of the
+// input never create elves.
+pub fn parse_calory_lists(input: &str, blank_lines: BlankLines) -> Result<Vec<Vec<i32>>, anyhow::Error>
+{
+    let mut calory_lists = Vec::new();
+    let mut current: Option<Vec<i32>> = None;
+    let mut blank_run = 0;
+
+    for (line_index, line) in input.lines().enumerate()
+    {
+        let line = line.trim();
+        if line.is_empty()
+        {
+            if let Some(list) = current.take()
+            {
+                calory_lists.push(list);
+            }
+            blank_run += 1;
+            continue;
+        }
+
+        if current.is_none()
+        {
+            if blank_lines == BlankLines::EmptyElves && !calory_lists.is_empty() && blank_run > 1
+            {
+                calory_lists.extend((1..blank_run).map(|_| Vec::new()));
+            }
+            blank_run = 0;
+        }
+
+        let calories = line.parse::<i32>()
+            .map_err(|e| anyhow::Error::msg(format!(
+                "elf {} on line {}: '{line}' is not a valid amount of calories ({e}).",
+                calory_lists.len() + 1, line_index + 1)))?;
+
+        current.get_or_insert_with(Vec::new).push(calories);
+    }
+
+    if let Some(list) = current
+    {
+        calory_lists.push(list);
+    }
+
+    Ok(calory_lists)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn grouping()
+    {
+        let input = "\r\n1000\r\n2000\r\n\r\n  \r\n3000\r\n \r\n\r\n";
+        assert_eq!(parse_calory_lists(input, BlankLines::Collapse).unwrap(), vec![vec![1000, 2000], vec![3000]]);
+        assert_eq!(parse_calory_lists(input, BlankLines::EmptyElves).unwrap(), vec![vec![1000, 2000], vec![], vec![3000]]);
+
+        let input = "1\n\n\n\n2\n3";
+        assert_eq!(parse_calory_lists(input, BlankLines::EmptyElves).unwrap(), vec![vec![1], vec![], vec![], vec![2, 3]]);
+
+        assert!(parse_calory_lists("", BlankLines::Collapse).unwrap().is_empty());
+        assert!(parse_calory_lists("\n \n", BlankLines::EmptyElves).unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_number()
+    {
+        let error = parse_calory_lists("1000\n\n2000\n20x0\n", BlankLines::Collapse).unwrap_err();
+        assert!(error.to_string().starts_with("elf 2 on line 4: '20x0'"));
+    }
+}
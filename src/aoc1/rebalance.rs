@@ -0,0 +1,284 @@
+// Redistributes individual snacks across the elves so the heaviest load is as small as possible
+// (multiprocessor scheduling). Small inputs are searched exactly, large ones use LPT. The exact
+// search starts from the LPT assignment, so when it hits its node limit the result is still at
+// least as good as LPT, just not proven optimal.
+
+const EXACT_SNACK_LIMIT: usize = 24;
+// Bounds the exact search, which keeps the best assignment found so far if it runs out.
+const SEARCH_NODE_LIMIT: usize = 2_000_000;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Rebalance
+{
+    pub assignment: Vec<Vec<i32>>,
+    pub max_load: i32,
+    pub lower_bound: i32,
+    pub is_exact: bool,
+}
+
+impl Rebalance
+{
+    fn new(assignment: Vec<Vec<i32>>, lower_bound: i32, is_exact: bool) -> Rebalance
+    {
+        let max_load = assignment.iter().map(|snacks| snacks.iter().sum::<i32>()).max().unwrap_or(0);
+        Rebalance { assignment, max_load, lower_bound, is_exact }
+    }
+
+    // Worst case ratio between `max_load` and the optimum. LPT guarantees 4/3 - 1/(3m).
+    pub fn approximation_bound(&self) -> f64
+    {
+        match self.is_exact
+        {
+            true => 1.0,
+            false => 4.0 / 3.0 - 1.0 / (3.0 * self.assignment.len() as f64),
+        }
+    }
+}
+
+pub fn rebalance(calory_lists: &[Vec<i32>]) -> Result<Rebalance, anyhow::Error>
+{
+    let num_snacks = calory_lists.iter().map(|list| list.len()).sum::<usize>();
+    match num_snacks <= EXACT_SNACK_LIMIT
+    {
+        true => rebalance_exact(calory_lists),
+        false => rebalance_lpt(calory_lists),
+    }
+}
+
+pub fn rebalance_lpt(calory_lists: &[Vec<i32>]) -> Result<Rebalance, anyhow::Error>
+{
+    let snacks = collect_snacks(calory_lists)?;
+    let lower_bound = lower_bound(&snacks, calory_lists.len());
+    Ok(Rebalance::new(assign_lpt(&snacks, calory_lists.len()), lower_bound, false))
+}
+
+pub fn rebalance_exact(calory_lists: &[Vec<i32>]) -> Result<Rebalance, anyhow::Error>
+{
+    let snacks = collect_snacks(calory_lists)?;
+    if snacks.len() > EXACT_SNACK_LIMIT
+    {
+        return Err(anyhow::Error::msg(format!(
+            "{} snacks are too many for an exact rebalance, the limit is {EXACT_SNACK_LIMIT}.",
+            snacks.len())));
+    }
+
+    let num_elves = calory_lists.len();
+    let lower_bound = lower_bound(&snacks, num_elves);
+
+    let best = assign_lpt(&snacks, num_elves);
+    let best_max = best.iter().map(|elf| elf.iter().sum::<i32>()).max().unwrap_or(0);
+    let remaining = snacks.iter()
+        .rev()
+        .scan(0, |sum, snack| { *sum += snack; Some(*sum) })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+
+    let mut search = Search
+    {
+        snacks: &snacks,
+        remaining,
+        lower_bound,
+        best,
+        best_max,
+        current: vec![Vec::new(); num_elves],
+        loads: vec![0; num_elves],
+        nodes: 0,
+    };
+
+    if search.best_max > lower_bound
+    {
+        search.branch(0);
+    }
+
+    let is_exact = search.nodes < SEARCH_NODE_LIMIT || search.best_max <= lower_bound;
+    Ok(Rebalance::new(search.best, lower_bound, is_exact))
+}
+
+struct Search<'a>
+{
+    snacks: &'a [i32],
+    // Calories of the snacks from each index on.
+    remaining: Vec<i32>,
+    lower_bound: i32,
+    best: Vec<Vec<i32>>,
+    best_max: i32,
+    current: Vec<Vec<i32>>,
+    loads: Vec<i32>,
+    nodes: usize,
+}
+
+impl Search<'_>
+{
+    // Returns true once the lower bound has been reached or the node limit hit, and the search
+    // can stop.
+    fn branch(&mut self, snack_index: usize) -> bool
+    {
+        self.nodes += 1;
+        if self.nodes >= SEARCH_NODE_LIMIT
+        {
+            return true;
+        }
+
+        let Some(&snack) = self.snacks.get(snack_index) else
+        {
+            let max = self.loads.iter().copied().max().unwrap_or(0);
+            if max < self.best_max
+            {
+                self.best_max = max;
+                self.best = self.current.clone();
+            }
+            return self.best_max <= self.lower_bound;
+        };
+
+        // The remaining snacks have to fit below the best maximum, and room on an elf smaller than
+        // the smallest snack is wasted.
+        let smallest = self.snacks.last().copied().unwrap_or(0);
+        let room = self.loads.iter()
+            .map(|load| self.best_max - 1 - load)
+            .filter(|room| *room >= smallest)
+            .sum::<i32>();
+        if room < self.remaining[snack_index]
+        {
+            return false;
+        }
+
+        for elf in 0..self.loads.len()
+        {
+            // Elves with the same load are interchangeable, only try the first of them.
+            if self.loads[..elf].contains(&self.loads[elf])
+            {
+                continue;
+            }
+
+            if self.loads[elf] + snack >= self.best_max
+            {
+                continue;
+            }
+
+            self.loads[elf] += snack;
+            self.current[elf].push(snack);
+            let done = self.branch(snack_index + 1);
+            self.current[elf].pop();
+            self.loads[elf] -= snack;
+
+            if done
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+fn collect_snacks(calory_lists: &[Vec<i32>]) -> Result<Vec<i32>, anyhow::Error>
+{
+    if calory_lists.is_empty()
+    {
+        return Err(anyhow::Error::msg("There are no elves to carry the snacks!"));
+    }
+
+    let mut snacks = calory_lists.iter().flatten().copied().collect::<Vec<_>>();
+    if let Some(snack) = snacks.iter().find(|snack| **snack < 0)
+    {
+        return Err(anyhow::Error::msg(format!("snack with '{snack}' calories can't be rebalanced.")));
+    }
+
+    snacks.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(snacks)
+}
+
+fn lower_bound(snacks: &[i32], num_elves: usize) -> i32
+{
+    let total = snacks.iter().sum::<i32>();
+    let average = (total + num_elves as i32 - 1) / num_elves as i32;
+    average.max(snacks.first().copied().unwrap_or(0))
+}
+
+// Expects the snacks sorted from largest to smallest.
+fn assign_lpt(snacks: &[i32], num_elves: usize) -> Vec<Vec<i32>>
+{
+    let mut assignment = vec![Vec::new(); num_elves];
+    let mut loads = vec![0; num_elves];
+
+    for &snack in snacks
+    {
+        let lightest = (0..num_elves).min_by_key(|elf| loads[*elf]).unwrap_or(0);
+        loads[lightest] += snack;
+        assignment[lightest].push(snack);
+    }
+
+    assignment
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn sorted_snacks(assignment: &[Vec<i32>]) -> Vec<i32>
+    {
+        let mut snacks = assignment.iter().flatten().copied().collect::<Vec<_>>();
+        snacks.sort_unstable();
+        snacks
+    }
+
+    #[test]
+    fn exact()
+    {
+        let lists = vec![
+            vec![1000, 2000, 3000],
+            vec![4000],
+            vec![5000, 6000],
+            vec![7000, 8000, 9000],
+            vec![10000],
+        ];
+
+        let result = rebalance_exact(&lists).unwrap();
+        assert_eq!(result.max_load, 11000);
+        assert_eq!(result.assignment.len(), 5);
+        assert_eq!(sorted_snacks(&result.assignment), sorted_snacks(&lists));
+
+        // LPT is not optimal here: it ends up with 3 + 2 + 2 on one elf.
+        let lists = vec![vec![3, 3, 2, 2, 2], vec![]];
+        assert_eq!(rebalance_lpt(&lists).unwrap().max_load, 7);
+        assert_eq!(rebalance_exact(&lists).unwrap().max_load, 6);
+    }
+
+    #[test]
+    fn lpt_within_bound()
+    {
+        let lists = (1..=40).map(|i| vec![(i * 37) % 101, (i * 53) % 89]).collect::<Vec<_>>();
+        let result = rebalance(&lists).unwrap();
+
+        assert!(!result.is_exact);
+        assert_eq!(sorted_snacks(&result.assignment), sorted_snacks(&lists));
+        assert!(result.max_load >= result.lower_bound);
+        assert!(result.max_load as f64 <= result.lower_bound as f64 * result.approximation_bound());
+    }
+
+    #[test]
+    fn exact_near_limit()
+    {
+        let lists = (0..7)
+            .map(|elf| (0..24).skip(elf).step_by(7).map(|i| 997 + (i * 7919) % 1013).collect())
+            .collect::<Vec<_>>();
+
+        let start = std::time::Instant::now();
+        let result = rebalance(&lists).unwrap();
+
+        assert!(start.elapsed().as_secs() < 10);
+        assert_eq!(sorted_snacks(&result.assignment), sorted_snacks(&lists));
+        assert!(result.max_load >= result.lower_bound);
+        assert!(result.max_load <= rebalance_lpt(&lists).unwrap().max_load);
+    }
+
+    #[test]
+    fn invalid()
+    {
+        assert!(rebalance(&[]).is_err());
+        assert!(rebalance(&[vec![1, -1]]).is_err());
+    }
+}
@@ -1,18 +1,367 @@
-mod round;
-mod parse;
+pub mod round;
+pub mod parse;
+pub mod game;
+pub mod scoring;
+pub mod decrypt;
+pub mod simulate;
+pub mod equilibrium;
+pub mod synthesize;
+pub mod encode;
+pub mod report;
+pub mod bulk;
+pub mod series;
+pub mod diff;
 
-pub fn run(input: String) -> Result<String, anyhow::Error>
+use game::Game;
+use parse::Interpretation;
+use scoring::Scoring;
+
+const SIMULATION_MATCHES: usize = 100;
+const SIMULATION_SEED: u64 = 2022;
+const SERIES_FORMATS: [series::MatchFormat; 3] =
+    [series::MatchFormat::BestOf(3), series::MatchFormat::BestOf(5), series::MatchFormat::FirstTo(10)];
+
+pub fn run(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
 {
-    let rounds: Vec<_> =
-        input.lines()
-        .map(|line| parse::parse_round(line))
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    solve(&input, &game, &scoring)
+}
+
+pub fn run_decrypt(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let guide = input.lines()
+        .map(|line| parse::parse_encrypted_round(line, &game))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let total_score =
-        rounds.iter()
-        .map(|round| round::calc_round_score(round))
-        .fold(0, |sum, score| sum + score);
+    let choices = decrypt::decrypt_choices(&guide, &game, &scoring)?;
+    let outcomes = decrypt::decrypt_outcomes(&guide, &game, &scoring)?;
+
+    let choice_name = |choice: &round::Choice| game.get_move(*choice).get_name().to_string();
+    let outcome_name = |result: &round::RoundResult| format!("{result:?}");
+
+    Ok([
+        "Symbols as moves:".to_string(),
+        report_decryption(&choices, choice_name),
+        "Symbols as outcomes:".to_string(),
+        report_decryption(&outcomes, outcome_name),
+    ].join("\n"))
+}
+
+// Same answers as `run`, read through the table-driven bulk scorer.
+pub fn run_bulk(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let choice_pair_score = bulk::BulkScorer::new(Interpretation::ChoicePair, &game, &scoring)?.score(input.as_bytes())?;
+    let desired_outcome_score = bulk::BulkScorer::new(Interpretation::DesiredOutcome, &game, &scoring)?.score(input.as_bytes())?;
+
+    Ok(format!("{choice_pair_score}\n{desired_outcome_score}"))
+}
+
+// Pits the guide's own moves and the built-in strategies against each other, every match lasting
+// as many rounds as the guide has.
+pub fn run_simulation(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let guide_choices = parse_rounds(&input, Interpretation::ChoicePair, &game)?.iter()
+        .map(|round| round.get_your())
+        .collect::<Vec<_>>();
+    let num_rounds = guide_choices.len();
+
+    let strategies = std::iter::once(Box::new(simulate::Guide::new(guide_choices)?) as Box<dyn simulate::Strategy>)
+        .chain(simulate::builtin_strategies(&game))
+        .collect::<Vec<_>>();
+
+    let matchups = simulate::run_tournament(&strategies, SIMULATION_MATCHES, num_rounds, &game, &scoring, SIMULATION_SEED)?;
+
+    Ok(matchups.iter()
+        .map(|matchup|
+            format!(
+                "{} vs {}: mean {:.1}, std dev {:.1}, min {}, max {}",
+                matchup.your, matchup.their,
+                matchup.your_scores.mean, matchup.your_scores.std_dev,
+                matchup.your_scores.min, matchup.your_scores.max
+            )
+        )
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+// Compares the optimal mixed strategy with the mix of moves the guide plays.
+pub fn run_equilibrium(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let payoff = equilibrium::payoff_matrix(&game, &scoring);
+    let equilibrium = equilibrium::solve_zero_sum(&payoff)?;
+
+    let rounds = parse_rounds(&input, Interpretation::ChoicePair, &game)?;
+    let mut guide_strategy = vec![0.0; game.num_moves()];
+    rounds.iter().for_each(|round| guide_strategy[round.get_your().get_index() as usize] += 1.0 / rounds.len() as f64);
+
+    let describe = |strategy: &[f64]|
+        game.choices().zip(strategy.iter())
+            .map(|(choice, p)| format!("{} {:.3}", game.get_move(choice).get_name(), p))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+    let mut lines = vec![
+        format!("value per round: {:.3}", equilibrium.value),
+        format!("your optimal strategy: {}", describe(&equilibrium.your_strategy)),
+        format!("their optimal strategy: {}", describe(&equilibrium.their_strategy)),
+    ];
+
+    if !rounds.is_empty()
+    {
+        let guide_value = equilibrium::worst_case_value(&payoff, &guide_strategy);
+        lines.push(format!("guide strategy: {}", describe(&guide_strategy)));
+        lines.push(format!("guide worst case per round: {guide_value:.3}, exploitable by {:.3}", equilibrium.value - guide_value));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+// The first line holds the target score, the following ones the opponent column of a guide.
+// Prints a guide reaching exactly that score.
+pub fn run_synthesis(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let (target_str, guide_str) = input.split_once('\n').unwrap_or((&input, ""));
+    let target = target_str.trim().parse::<i32>()?;
+
+    let their = guide_str.lines()
+        .map(|line| parse::parse_their_column(line, &game))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let synthesis = synthesize::synthesize(&their, target, &game, &scoring)?
+        .ok_or_else(|| anyhow::Error::msg(format!("no guide against this opponent scores exactly {target}.")))?;
+
+    let rounds = their.iter().zip(synthesis.choices.iter())
+        .map(|(their, your)| round::Round::new(*their, round::Your(*your)))
+        .collect::<Vec<_>>();
+
+    Ok(encode::Encoder::new(&game, Interpretation::ChoicePair).encode_guide(&rounds))
+}
+
+pub fn run_report(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    [Interpretation::ChoicePair, Interpretation::DesiredOutcome].into_iter()
+        .map(|interpretation|
+        {
+            let rounds = parse_rounds(&input, interpretation, &game)?;
+            Ok(report_guide(&rounds, interpretation, &game, &scoring))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()
+        .map(|reports| reports.join("\n\n"))
+}
+
+fn report_guide(rounds: &[round::Round], interpretation: Interpretation, game: &Game, scoring: &Scoring) -> String
+{
+    let name = |choice: round::Choice| game.get_move(choice).get_name();
+
+    let round_lines = report::report_rounds(rounds, game, scoring).into_iter()
+        .map(|report|
+            format!(
+                "{:>5} {:>9} {:>9} {:>6} {:>5} {:>7} {:>5} {:>7}",
+                report.line_number, name(report.their), name(report.your), format!("{:?}", report.result),
+                report.shape_points, report.outcome_points, report.bonus_points, report.running_total
+            )
+        );
+
+    let count_lines = game.choices().zip(report::count_outcomes(rounds, game))
+        .map(|(choice, counts)|
+            format!("{:>9}: {} wins, {} draws, {} losses", name(choice), counts.wins, counts.draws, counts.losses)
+        );
+
+    std::iter::once(format!("{interpretation:?}"))
+        .chain(std::iter::once(format!(
+            "{:>5} {:>9} {:>9} {:>6} {:>5} {:>7} {:>5} {:>7}",
+            "line", "their", "your", "result", "shape", "outcome", "bonus", "total")))
+        .chain(round_lines)
+        .chain(count_lines)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Plays the desired-outcome guide as a series of matches in every format.
+pub fn run_series(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+    let rounds = parse_rounds(&input, Interpretation::DesiredOutcome, &game)?;
+
+    SERIES_FORMATS.iter()
+        .map(|format|
+        {
+            let series = series::play_series(&rounds, &game, &scoring, &series::SeriesRules::new(*format))?;
+            let unfinished = series.matches.iter().filter(|outcome| outcome.winner.is_none()).count();
+            Ok(format!(
+                "{format:?}: {} matches, {} won, {} lost, {unfinished} unfinished, longest winning streak {}, score {}",
+                series.matches.len(), series.matches_won, series.matches_lost, series.longest_winning_streak, series.total_score
+            ))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()
+        .map(|lines| lines.join("\n"))
+}
+
+// Takes two choice-pair guides separated by an empty line.
+pub fn run_diff(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let (first, second) = split_guides(&input)?;
+    let first = parse_rounds(&first, Interpretation::ChoicePair, &game)?;
+    let second = parse_rounds(&second, Interpretation::ChoicePair, &game)?;
+
+    report_diff(&first, &second, &game, &scoring)
+}
+
+// The guides are separated by the first run of empty lines.
+fn split_guides(input: &str) -> Result<(String, String), anyhow::Error>
+{
+    let lines = input.lines().collect::<Vec<_>>();
+    let is_blank = |line: &&str| line.trim().is_empty();
+
+    let split = lines.iter().position(is_blank)
+        .ok_or_else(|| anyhow::Error::msg("Input doesn't have an empty line to split the two guides."))?;
+    let second_start = lines[split..].iter().position(|line| !is_blank(line)).map_or(lines.len(), |start| split + start);
 
-    Ok(total_score.to_string())
+    Ok((lines[..split].join("\n"), lines[second_start..].join("\n")))
 }
 
+// Compares the choice-pair reading of a guide with its desired-outcome reading.
+pub fn run_interpretation_diff(input: String, scoring_path: Option<&str>) -> Result<String, anyhow::Error>
+{
+    let game = Game::rock_paper_scissors();
+    let scoring = load_scoring(scoring_path, &game)?;
+
+    let first = parse_rounds(&input, Interpretation::ChoicePair, &game)?;
+    let second = parse_rounds(&input, Interpretation::DesiredOutcome, &game)?;
+
+    report_diff(&first, &second, &game, &scoring)
+}
+
+fn report_diff(first: &[round::Round], second: &[round::Round], game: &Game, scoring: &Scoring) -> Result<String, anyhow::Error>
+{
+    let diffs = diff::diff_guides(first, second, game, scoring)?;
+    let name = |choice: round::Choice| game.get_move(choice).get_name();
+
+    let diff_lines = diffs.iter()
+        .filter(|diff| diff.is_different())
+        .map(|diff|
+            format!(
+                "line {}: against {}, {} {:?} {} | {} {:?} {}, delta {:+}, cumulative {:+}",
+                diff.first.line_number, name(diff.first.their),
+                name(diff.first.your), diff.first.result, diff.first.get_points(),
+                name(diff.second.your), diff.second.result, diff.second.get_points(),
+                diff.delta, diff.cumulative
+            )
+        );
+
+    let summary = format!(
+        "{} of {} rounds differ, total advantage of the second guide {:+}",
+        diffs.iter().filter(|diff| diff.is_different()).count(), diffs.len(),
+        diffs.last().map_or(0, |diff| diff.cumulative)
+    );
+
+    Ok(diff_lines.chain(std::iter::once(summary)).collect::<Vec<_>>().join("\n"))
+}
+
+// The 2022 rules, unless a scoring file is given.
+fn load_scoring(scoring_path: Option<&str>, game: &Game) -> Result<Scoring, anyhow::Error>
+{
+    match scoring_path
+    {
+        Some(path) => Scoring::load(path, game),
+        None => Ok(Scoring::standard(game)),
+    }
+}
+
+fn solve(input: &str, game: &Game, scoring: &Scoring) -> Result<String, anyhow::Error>
+{
+    // Part 1
+    let choice_pair_score = calc_total_score(input, Interpretation::ChoicePair, game, scoring)?;
+
+    // Part 2
+    let desired_outcome_score = calc_total_score(input, Interpretation::DesiredOutcome, game, scoring)?;
+
+    Ok(format!("{choice_pair_score}\n{desired_outcome_score}"))
+}
+
+fn calc_total_score(input: &str, interpretation: Interpretation, game: &Game, scoring: &Scoring) -> Result<i32, anyhow::Error>
+{
+    let rounds = parse_rounds(input, interpretation, game)?;
+
+    Ok(scoring.calc_total_score(&rounds, game))
+}
+
+fn parse_rounds(input: &str, interpretation: Interpretation, game: &Game) -> Result<Vec<round::Round>, anyhow::Error>
+{
+    input.lines()
+        .map(|line| parse::parse_round(line, interpretation, game))
+        .collect::<Result<Vec<_>, _>>()
+}
+
+fn report_decryption<T>(decryption: &decrypt::Decryption<T>, name: impl Fn(&T) -> String) -> String
+{
+    let describe = |mapping: &decrypt::MappingScore<T>|
+        format!(
+            "{}: {}",
+            mapping.mapping.iter().map(|(symbol, target)| format!("{symbol}={}", name(target))).collect::<Vec<_>>().join(" "),
+            mapping.score
+        );
+
+    let uniqueness = |is_unique| if is_unique { "unique" } else { "not unique" };
+
+    decryption.scores.iter()
+        .map(describe)
+        .chain([
+            format!("best {} ({})", describe(decryption.get_best()), uniqueness(decryption.is_best_unique())),
+            format!("worst {} ({})", describe(decryption.get_worst()), uniqueness(decryption.is_worst_unique())),
+        ])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn interpretations()
+    {
+        let input = "A Y\r\nB X\r\nC Z\r\n";
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        assert_eq!(calc_total_score(input, Interpretation::ChoicePair, &game, &scoring).unwrap(), 15);
+        assert_eq!(calc_total_score(input, Interpretation::DesiredOutcome, &game, &scoring).unwrap(), 12);
+        assert_eq!(solve(input, &game, &scoring).unwrap(), "15\n12");
+    }
+
+    #[test]
+    fn guide_split()
+    {
+        let (first, second) = split_guides("A Y\r\nB X\r\n\r\n \r\n\r\nC Z\r\nA X\r\n").unwrap();
+        assert_eq!(first, "A Y\nB X");
+        assert_eq!(second, "C Z\nA X");
+
+        assert_eq!(split_guides("A Y\n\n\n").unwrap(), ("A Y".to_string(), String::new()));
+        assert!(split_guides("A Y\nB X").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use super::game::Game;
+use super::parse::{Interpretation, RESULT_SYMBOLS};
+use super::round::{self, Round, Their, Your};
+use super::scoring::Scoring;
+
+const INVALID: u8 = u8::MAX;
+
+// Scores guides byte by byte from a precomputed table instead of building `Round`s. Every line has
+// to be exactly "<their symbol> <second symbol>", ended by "\n", "\r\n" or the end of the input.
+pub struct BulkScorer
+{
+    their_lookup: [u8; 256],
+    second_lookup: [u8; 256],
+    num_second: usize,
+    table: Vec<i64>,
+}
+
+impl BulkScorer
+{
+    pub fn new(interpretation: Interpretation, game: &Game, scoring: &Scoring) -> Result<BulkScorer, anyhow::Error>
+    {
+        if scoring.has_streak_bonuses()
+        {
+            return Err(anyhow::Error::msg("the bulk scorer can't apply streak bonuses, use the typed path."));
+        }
+
+        let mut their_lookup = [INVALID; 256];
+        for choice in game.choices()
+        {
+            their_lookup[to_byte(game.get_move(choice).get_their_symbol())? as usize] = choice.get_index();
+        }
+
+        let mut second_lookup = [INVALID; 256];
+        let mut table = Vec::new();
+        let num_second = match interpretation
+        {
+            Interpretation::ChoicePair =>
+            {
+                for your in game.choices()
+                {
+                    second_lookup[to_byte(game.get_move(your).get_your_symbol())? as usize] = your.get_index();
+                }
+
+                for their in game.choices()
+                {
+                    table.extend(game.choices()
+                        .map(|your| round::calc_round_score(&Round::new(Their(their), Your(your)), game, scoring) as i64));
+                }
+
+                game.num_moves()
+            },
+            Interpretation::DesiredOutcome =>
+            {
+                for (index, (symbol, _)) in RESULT_SYMBOLS.iter().enumerate()
+                {
+                    second_lookup[to_byte(*symbol)? as usize] = index as u8;
+                }
+
+                for their in game.choices()
+                {
+                    table.extend(RESULT_SYMBOLS.iter()
+                        .map(|(_, result)| game.get_choice_for_result(their, *result))
+                        .map(|your| round::calc_round_score(&Round::new(Their(their), Your(your)), game, scoring) as i64));
+                }
+
+                RESULT_SYMBOLS.len()
+            },
+        };
+
+        Ok(BulkScorer { their_lookup, second_lookup, num_second, table })
+    }
+
+    pub fn score(&self, input: &[u8]) -> Result<i64, anyhow::Error>
+    {
+        let mut total = 0i64;
+        let mut pos = 0;
+        let mut line_number = 1;
+
+        while pos < input.len()
+        {
+            let line = &input[pos..input.len().min(pos + 3)];
+            let (their, second) = match line
+            {
+                [their, b' ', second] => (self.their_lookup[*their as usize], self.second_lookup[*second as usize]),
+                _ => (INVALID, INVALID),
+            };
+
+            if their == INVALID || second == INVALID
+            {
+                return Err(self.line_error(input, pos, line_number));
+            }
+
+            total += self.table[their as usize * self.num_second + second as usize];
+            pos += 3;
+
+            match input.get(pos..input.len().min(pos + 2))
+            {
+                Some([b'\n', ..]) => pos += 1,
+                Some([b'\r', b'\n']) => pos += 2,
+                Some([]) => (),
+                _ => return Err(self.line_error(input, pos - 3, line_number)),
+            }
+
+            line_number += 1;
+        }
+
+        Ok(total)
+    }
+
+    fn line_error(&self, input: &[u8], line_start: usize, line_number: usize) -> anyhow::Error
+    {
+        let line_end = input[line_start..].iter().position(|byte| *byte == b'\n').map_or(input.len(), |end| line_start + end);
+        let line = String::from_utf8_lossy(&input[line_start..line_end]);
+        anyhow::Error::msg(format!("line {line_number}: '{}' is not a valid round.", line.trim_end()))
+    }
+}
+
+fn to_byte(symbol: char) -> Result<u8, anyhow::Error>
+{
+    match symbol.is_ascii()
+    {
+        true => Ok(symbol as u8),
+        false => Err(anyhow::Error::msg(format!("symbol '{symbol}' isn't ascii, the bulk scorer can't read it."))),
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::parse_round;
+    use super::super::simulate::Rng;
+
+    fn generate_guide(num_rounds: usize, line_ending: &str) -> String
+    {
+        let mut rng = Rng::new(1);
+        (0..num_rounds)
+            .map(|_| format!("{} {}", ['A', 'B', 'C'][rng.next_below(3)], ['X', 'Y', 'Z'][rng.next_below(3)]))
+            .collect::<Vec<_>>()
+            .join(line_ending)
+    }
+
+    fn reference_score(input: &str, interpretation: Interpretation, game: &Game, scoring: &Scoring) -> i64
+    {
+        let rounds = input.lines()
+            .map(|line| parse_round(line, interpretation, game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        scoring.calc_total_score(&rounds, game) as i64
+    }
+
+    #[test]
+    fn matches_typed_path()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+
+        for interpretation in [Interpretation::ChoicePair, Interpretation::DesiredOutcome]
+        {
+            let scorer = BulkScorer::new(interpretation, &game, &scoring).unwrap();
+            for line_ending in ["\n", "\r\n"]
+            {
+                let input = generate_guide(1000, line_ending);
+                assert_eq!(scorer.score(input.as_bytes()).unwrap(), reference_score(&input, interpretation, &game, &scoring));
+
+                let input = input + line_ending;
+                assert_eq!(scorer.score(input.as_bytes()).unwrap(), reference_score(&input, interpretation, &game, &scoring));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_lines()
+    {
+        let game = Game::rock_paper_scissors();
+        let scorer = BulkScorer::new(Interpretation::ChoicePair, &game, &Scoring::standard(&game)).unwrap();
+
+        assert_eq!(scorer.score(b"A Y\nB  X\nC Z").unwrap_err().to_string(), "line 2: 'B  X' is not a valid round.");
+        assert_eq!(scorer.score(b"A Y\r\nD X\r\n").unwrap_err().to_string(), "line 2: 'D X' is not a valid round.");
+        assert_eq!(scorer.score(b"A Y\n\nC Z").unwrap_err().to_string(), "line 2: '' is not a valid round.");
+        assert_eq!(scorer.score(b"A YZ").unwrap_err().to_string(), "line 1: 'A YZ' is not a valid round.");
+        assert_eq!(scorer.score(b"").unwrap(), 0);
+    }
+
+    // cargo test --release bulk_throughput -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bulk_throughput()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let scorer = BulkScorer::new(Interpretation::DesiredOutcome, &game, &scoring).unwrap();
+        let input = generate_guide(50_000_000, "\n");
+
+        let start = std::time::Instant::now();
+        let score = scorer.score(input.as_bytes()).unwrap();
+        let seconds = start.elapsed().as_secs_f64();
+
+        println!("{score} in {seconds:.3}s, {:.0} MB/s", input.len() as f64 / seconds / 1e6);
+    }
+}
@@ -0,0 +1,146 @@
+use super::game::Game;
+use super::parse::RESULT_SYMBOLS;
+use super::round::{Choice, Round, RoundResult, Their, Your};
+use super::scoring::Scoring;
+
+#[derive(Debug,Clone)]
+pub struct MappingScore<T>
+{
+    pub mapping: Vec<(char, T)>,
+    pub score: i32,
+}
+
+#[derive(Debug)]
+pub struct Decryption<T>
+{
+    pub scores: Vec<MappingScore<T>>,
+}
+
+impl<T> Decryption<T>
+{
+    pub fn get_best(&self) -> &MappingScore<T>
+    {
+        self.scores.iter().max_by_key(|mapping| mapping.score).expect("there is always at least one mapping")
+    }
+
+    pub fn get_worst(&self) -> &MappingScore<T>
+    {
+        self.scores.iter().min_by_key(|mapping| mapping.score).expect("there is always at least one mapping")
+    }
+
+    pub fn is_best_unique(&self) -> bool
+    {
+        let best = self.get_best().score;
+        self.scores.iter().filter(|mapping| mapping.score == best).count() == 1
+    }
+
+    pub fn is_worst_unique(&self) -> bool
+    {
+        let worst = self.get_worst().score;
+        self.scores.iter().filter(|mapping| mapping.score == worst).count() == 1
+    }
+}
+
+// Tries every bijection of the game's second column symbols onto its moves.
+pub fn decrypt_choices(guide: &[(Their, char)], game: &Game, scoring: &Scoring) -> Result<Decryption<Choice>, anyhow::Error>
+{
+    let symbols = game.choices().map(|choice| game.get_move(choice).get_your_symbol()).collect::<Vec<_>>();
+    let targets = game.choices().collect::<Vec<_>>();
+
+    decrypt(guide, &symbols, &targets, |their, your| Round::new(their, Your(your)), game, scoring)
+}
+
+// Tries every bijection of the result symbols onto lose/draw/win.
+pub fn decrypt_outcomes(guide: &[(Their, char)], game: &Game, scoring: &Scoring) -> Result<Decryption<RoundResult>, anyhow::Error>
+{
+    let symbols = RESULT_SYMBOLS.iter().map(|(symbol, _)| *symbol).collect::<Vec<_>>();
+    let targets = RESULT_SYMBOLS.iter().map(|(_, result)| *result).collect::<Vec<_>>();
+
+    decrypt(
+        guide, &symbols, &targets,
+        |their, result| Round::new(their, Your(game.get_choice_for_result(their.0, result))),
+        game, scoring)
+}
+
+fn decrypt<T: Copy>(
+    guide: &[(Their, char)],
+    symbols: &[char],
+    targets: &[T],
+    to_round: impl Fn(Their, T) -> Round,
+    game: &Game,
+    scoring: &Scoring) -> Result<Decryption<T>, anyhow::Error>
+{
+    let symbol_indices = guide.iter()
+        .map(|(_, symbol)|
+            symbols.iter().position(|s| s == symbol)
+                .ok_or_else(|| anyhow::Error::msg(format!("'{symbol}' is not one of the symbols {symbols:?}.")))
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let scores = permutations(targets.len()).into_iter()
+        .map(|permutation|
+        {
+            let rounds = guide.iter().zip(symbol_indices.iter())
+                .map(|((their, _), symbol_index)| to_round(*their, targets[permutation[*symbol_index]]))
+                .collect::<Vec<_>>();
+
+            MappingScore
+            {
+                mapping: symbols.iter().zip(permutation.iter()).map(|(symbol, target)| (*symbol, targets[*target])).collect(),
+                score: scoring.calc_total_score(&rounds, game),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Decryption { scores })
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>>
+{
+    match n
+    {
+        0 => vec![Vec::new()],
+        _ => permutations(n - 1).into_iter()
+            .flat_map(|permutation|
+                (0..n).map(move |position|
+                {
+                    let mut extended = permutation.clone();
+                    extended.insert(position, n - 1);
+                    extended
+                })
+            )
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::parse_encrypted_round;
+
+    #[test]
+    fn mappings()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let guide = ["A Y", "B X", "C Z"].iter()
+            .map(|line| parse_encrypted_round(line, &game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let choices = decrypt_choices(&guide, &game, &scoring).unwrap();
+        assert_eq!(choices.scores.len(), 6);
+        assert!(choices.scores.iter().any(|mapping| mapping.score == 15
+            && mapping.mapping == vec![('X', Choice::ROCK), ('Y', Choice::PAPER), ('Z', Choice::SCISSORS)]));
+        assert_eq!(choices.get_best().score, 24);
+        assert!(choices.is_best_unique());
+        assert_eq!(choices.get_worst().score, 6);
+
+        let outcomes = decrypt_outcomes(&guide, &game, &scoring).unwrap();
+        assert_eq!(outcomes.scores.len(), 6);
+        assert!(outcomes.scores.iter().any(|mapping| mapping.score == 12));
+        assert_eq!(outcomes.get_best().score, 18);
+        assert!(outcomes.is_best_unique());
+    }
+}
@@ -0,0 +1,79 @@
+use super::game::Game;
+use super::report::{self, RoundReport};
+use super::round::Round;
+use super::scoring::Scoring;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct RoundDiff
+{
+    pub first: RoundReport,
+    pub second: RoundReport,
+    // Points the second guide gained over the first in this round, and up to this round.
+    pub delta: i32,
+    pub cumulative: i32,
+}
+
+impl RoundDiff
+{
+    pub fn is_different(&self) -> bool
+    {
+        self.first.your != self.second.your || self.first.result != self.second.result || self.delta != 0
+    }
+}
+
+// Both guides have to be played against the same opponent moves, line by line.
+pub fn diff_guides(first: &[Round], second: &[Round], game: &Game, scoring: &Scoring) -> Result<Vec<RoundDiff>, anyhow::Error>
+{
+    if first.len() != second.len()
+    {
+        return Err(anyhow::Error::msg(format!("the guides have {} and {} rounds.", first.len(), second.len())));
+    }
+
+    if let Some(index) = first.iter().zip(second.iter()).position(|(a, b)| a.get_their() != b.get_their())
+    {
+        return Err(anyhow::Error::msg(format!("the guides play against different opponent moves on line {}.", index + 1)));
+    }
+
+    let first_reports = report::report_rounds(first, game, scoring);
+    let second_reports = report::report_rounds(second, game, scoring);
+
+    Ok(first_reports.into_iter().zip(second_reports)
+        .scan(0, |previous_cumulative, (first, second)|
+        {
+            let cumulative = second.running_total - first.running_total;
+            let delta = cumulative - *previous_cumulative;
+            *previous_cumulative = cumulative;
+            Some(RoundDiff { first, second, delta, cumulative })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::{parse_round, Interpretation};
+
+    #[test]
+    fn interpretations()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let parse = |interpretation| ["A Y", "B X", "C Z"].iter()
+            .map(|line| parse_round(line, interpretation, &game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let diffs = diff_guides(&parse(Interpretation::ChoicePair), &parse(Interpretation::DesiredOutcome), &game, &scoring).unwrap();
+        assert_eq!(diffs.iter().map(|diff| diff.delta).collect::<Vec<_>>(), vec![-4, 0, 1]);
+        assert_eq!(diffs.iter().map(|diff| diff.cumulative).collect::<Vec<_>>(), vec![-4, -4, -3]);
+        assert_eq!(diffs.iter().map(|diff| diff.is_different()).collect::<Vec<_>>(), vec![true, false, true]);
+
+        let other_opponent = ["A Y", "C X", "C Z"].iter()
+            .map(|line| parse_round(line, Interpretation::ChoicePair, &game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(diff_guides(&parse(Interpretation::ChoicePair), &other_opponent, &game, &scoring).is_err());
+        assert!(diff_guides(&parse(Interpretation::ChoicePair), &other_opponent[..2], &game, &scoring).is_err());
+    }
+}
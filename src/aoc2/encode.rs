@@ -0,0 +1,90 @@
+use super::game::Game;
+use super::parse::Interpretation;
+use super::round::{Choice, Round, RoundResult};
+
+// Turns rounds back into guide lines, so that parsing with the same game and interpretation gives
+// the same rounds again. `Choice` and `Round` don't implement `Display` because their symbols come
+// from the `Game`, so they are encoded here; `RoundResult` symbols are fixed and it has `Display`.
+pub struct Encoder<'a>
+{
+    game: &'a Game,
+    interpretation: Interpretation,
+}
+
+impl<'a> Encoder<'a>
+{
+    pub fn new(game: &'a Game, interpretation: Interpretation) -> Encoder<'a>
+    {
+        Encoder { game, interpretation }
+    }
+
+    pub fn encode_their_choice(&self, choice: Choice) -> char
+    {
+        self.game.get_move(choice).get_their_symbol()
+    }
+
+    pub fn encode_your_choice(&self, choice: Choice) -> char
+    {
+        self.game.get_move(choice).get_your_symbol()
+    }
+
+    pub fn encode_result(&self, result: RoundResult) -> char
+    {
+        result.get_symbol()
+    }
+
+    pub fn encode_round(&self, round: &Round) -> String
+    {
+        let second = match self.interpretation
+        {
+            Interpretation::ChoicePair => self.encode_your_choice(round.get_your()),
+            Interpretation::DesiredOutcome => self.encode_result(round.get_result(self.game)),
+        };
+
+        format!("{} {second}", self.encode_their_choice(round.get_their()))
+    }
+
+    pub fn encode_guide(&self, rounds: &[Round]) -> String
+    {
+        rounds.iter()
+            .map(|round| self.encode_round(round))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::{parse_round, RESULT_SYMBOLS};
+
+    #[test]
+    fn round_trip()
+    {
+        for game in [Game::rock_paper_scissors(), Game::rock_paper_scissors_lizard_spock()]
+        {
+            let their_symbols = game.choices().map(|choice| game.get_move(choice).get_their_symbol()).collect::<Vec<_>>();
+            let your_symbols = game.choices().map(|choice| game.get_move(choice).get_your_symbol()).collect::<Vec<_>>();
+            let result_symbols = RESULT_SYMBOLS.iter().map(|(symbol, _)| *symbol).collect::<Vec<_>>();
+
+            for (interpretation, second_symbols) in [(Interpretation::ChoicePair, your_symbols), (Interpretation::DesiredOutcome, result_symbols)]
+            {
+                let encoder = Encoder::new(&game, interpretation);
+                let guide = their_symbols.iter()
+                    .flat_map(|their| second_symbols.iter().map(move |second| format!("{their} {second}")))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                let rounds = guide.lines()
+                    .map(|line| parse_round(line, interpretation, &game))
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+
+                assert_eq!(encoder.encode_guide(&rounds), guide);
+            }
+        }
+
+        assert_eq!(RoundResult::Win.to_string(), "Z");
+    }
+}
@@ -0,0 +1,165 @@
+use super::game::Game;
+use super::round::{self, Round, Their, Your};
+use super::scoring::Scoring;
+
+const EPSILON: f64 = 1e-9;
+
+// Mixed strategies are probabilities indexed by choice. You maximize your score, they minimize it.
+#[derive(Debug,Clone,PartialEq)]
+pub struct Equilibrium
+{
+    pub your_strategy: Vec<f64>,
+    pub their_strategy: Vec<f64>,
+    pub value: f64,
+}
+
+// Your score for one round, indexed by [your choice][their choice]. Streak bonuses depend on the
+// order of the rounds and are left out.
+pub fn payoff_matrix(game: &Game, scoring: &Scoring) -> Vec<Vec<f64>>
+{
+    game.choices()
+        .map(|your|
+            game.choices()
+            .map(|their| round::calc_round_score(&Round::new(Their(their), Your(your)), game, scoring) as f64)
+            .collect()
+        )
+        .collect()
+}
+
+// The score you can count on per round when playing `your_strategy` against the opponent's best reply.
+pub fn worst_case_value(payoff: &[Vec<f64>], your_strategy: &[f64]) -> f64
+{
+    (0..payoff[0].len())
+        .map(|their| your_strategy.iter().zip(payoff.iter()).map(|(p, row)| p * row[their]).sum::<f64>())
+        .fold(f64::INFINITY, f64::min)
+}
+
+// The score the opponent concedes at most per round when playing `their_strategy` against your best reply.
+pub fn best_case_value(payoff: &[Vec<f64>], their_strategy: &[f64]) -> f64
+{
+    payoff.iter()
+        .map(|row| their_strategy.iter().zip(row.iter()).map(|(q, score)| q * score).sum::<f64>())
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+// Solves the zero-sum game with the simplex method. After shifting every payoff to be positive,
+// their strategy is y / sum(y) for: maximize sum(y) subject to payoff * y <= 1, y >= 0, and
+// yours comes out of the dual values of the same tableau.
+pub fn solve_zero_sum(payoff: &[Vec<f64>]) -> Result<Equilibrium, anyhow::Error>
+{
+    let num_yours = payoff.len();
+    let num_theirs = payoff.first().map_or(0, |row| row.len());
+    if num_yours == 0 || num_theirs == 0 || payoff.iter().any(|row| row.len() != num_theirs)
+    {
+        return Err(anyhow::Error::msg("the payoff matrix has to be rectangular and not empty."));
+    }
+
+    let min_payoff = payoff.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - min_payoff;
+
+    // Columns: their choices, then one slack per constraint, then the right hand side.
+    let num_columns = num_theirs + num_yours + 1;
+    let mut tableau = payoff.iter().enumerate()
+        .map(|(your, row)|
+        {
+            let mut tableau_row = row.iter().map(|score| score + shift).collect::<Vec<_>>();
+            tableau_row.extend((0..num_yours).map(|slack| if slack == your { 1.0 } else { 0.0 }));
+            tableau_row.push(1.0);
+            tableau_row
+        })
+        .collect::<Vec<_>>();
+
+    let mut objective = vec![-1.0; num_theirs];
+    objective.resize(num_columns, 0.0);
+    tableau.push(objective);
+
+    let mut basis = (num_theirs..num_theirs + num_yours).collect::<Vec<_>>();
+
+    // Bland's rule: lowest entering and leaving indices, so the method can't cycle.
+    while let Some(entering) = (0..num_columns - 1).find(|column| tableau[num_yours][*column] < -EPSILON)
+    {
+        let leaving = (0..num_yours)
+            .filter(|row| tableau[*row][entering] > EPSILON)
+            .min_by(|a, b|
+            {
+                let ratio_a = tableau[*a][num_columns - 1] / tableau[*a][entering];
+                let ratio_b = tableau[*b][num_columns - 1] / tableau[*b][entering];
+                ratio_a.partial_cmp(&ratio_b).unwrap().then(basis[*a].cmp(&basis[*b]))
+            })
+            .ok_or_else(|| anyhow::Error::msg("the payoff matrix gives an unbounded program."))?;
+
+        let pivot = tableau[leaving][entering];
+        tableau[leaving].iter_mut().for_each(|value| *value /= pivot);
+        let pivot_row = tableau[leaving].clone();
+
+        for (row_index, row) in tableau.iter_mut().enumerate()
+        {
+            let factor = row[entering];
+            if row_index != leaving && factor != 0.0
+            {
+                row.iter_mut().zip(pivot_row.iter()).for_each(|(value, pivot_value)| *value -= factor * pivot_value);
+            }
+        }
+
+        basis[leaving] = entering;
+    }
+
+    let sum = tableau[num_yours][num_columns - 1];
+    let scale = 1.0 / sum;
+
+    let mut their_strategy = vec![0.0; num_theirs];
+    for (row, variable) in basis.iter().enumerate()
+    {
+        if *variable < num_theirs
+        {
+            their_strategy[*variable] = tableau[row][num_columns - 1] * scale;
+        }
+    }
+
+    let your_strategy = (0..num_yours)
+        .map(|your| tableau[num_yours][num_theirs + your] * scale)
+        .collect::<Vec<_>>();
+
+    Ok(Equilibrium { your_strategy, their_strategy, value: scale - shift })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn assert_close(a: f64, b: f64)
+    {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn classic_zero_sum()
+    {
+        let payoff = vec![vec![0.0, -1.0, 1.0], vec![1.0, 0.0, -1.0], vec![-1.0, 1.0, 0.0]];
+        let equilibrium = solve_zero_sum(&payoff).unwrap();
+
+        assert_close(equilibrium.value, 0.0);
+        equilibrium.your_strategy.iter().for_each(|p| assert_close(*p, 1.0 / 3.0));
+        equilibrium.their_strategy.iter().for_each(|q| assert_close(*q, 1.0 / 3.0));
+    }
+
+    #[test]
+    fn tournament_scores()
+    {
+        for game in [Game::rock_paper_scissors(), Game::rock_paper_scissors_lizard_spock()]
+        {
+            let payoff = payoff_matrix(&game, &Scoring::standard(&game));
+            let equilibrium = solve_zero_sum(&payoff).unwrap();
+
+            assert_close(equilibrium.your_strategy.iter().sum(), 1.0);
+            assert_close(equilibrium.their_strategy.iter().sum(), 1.0);
+            assert_close(worst_case_value(&payoff, &equilibrium.your_strategy), equilibrium.value);
+            assert_close(best_case_value(&payoff, &equilibrium.their_strategy), equilibrium.value);
+        }
+
+        let game = Game::rock_paper_scissors();
+        let payoff = payoff_matrix(&game, &Scoring::standard(&game));
+        assert_close(worst_case_value(&payoff, &[1.0, 0.0, 0.0]), 1.0);
+    }
+}
@@ -0,0 +1,217 @@
+use super::round::{Choice, RoundResult};
+
+#[derive(Debug,Clone)]
+pub struct Move
+{
+    name: String,
+    their_symbol: char,
+    your_symbol: char,
+}
+
+// A balanced tournament game: every move draws against itself and beats exactly half of the
+// other moves, like Rock-Paper-Scissors.
+#[derive(Debug,Clone)]
+pub struct Game
+{
+    moves: Vec<Move>,
+    beats: Vec<Vec<bool>>,
+}
+
+impl Move
+{
+    pub fn new(name: &str, their_symbol: char, your_symbol: char) -> Move
+    {
+        Move { name: name.to_string(), their_symbol, your_symbol }
+    }
+
+    pub fn get_name(&self) -> &str { &self.name }
+
+    pub fn get_their_symbol(&self) -> char { self.their_symbol }
+
+    pub fn get_your_symbol(&self) -> char { self.your_symbol }
+}
+
+impl Game
+{
+    // `dominance` lists (winner, loser) pairs by move name.
+    pub fn new(moves: Vec<Move>, dominance: &[(&str, &str)]) -> Result<Game, anyhow::Error>
+    {
+        let find_move = |name: &str|
+            moves.iter().position(|m| m.name == name)
+                .ok_or_else(|| anyhow::Error::msg(format!("move '{name}' is not part of the game.")));
+
+        let mut beats = vec![vec![false; moves.len()]; moves.len()];
+        for (winner, loser) in dominance
+        {
+            beats[find_move(winner)?][find_move(loser)?] = true;
+        }
+
+        Game::from_matrix(moves, beats)
+    }
+
+    // Every move beats the half of the moves listed right before it, wrapping around.
+    pub fn cyclic(moves: Vec<Move>) -> Result<Game, anyhow::Error>
+    {
+        let num_moves = moves.len();
+        let beats = (0..num_moves)
+            .map(|winner|
+                (0..num_moves)
+                .map(|loser| (1..=num_moves / 2).any(|offset| (loser + offset) % num_moves == winner))
+                .collect::<Vec<_>>()
+            )
+            .collect::<Vec<_>>();
+
+        Game::from_matrix(moves, beats)
+    }
+
+    pub fn rock_paper_scissors() -> Game
+    {
+        Game::cyclic(vec![
+            Move::new("Rock", 'A', 'X'),
+            Move::new("Paper", 'B', 'Y'),
+            Move::new("Scissors", 'C', 'Z'),
+        ]).expect("Rock-Paper-Scissors is a balanced game")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Game
+    {
+        Game::cyclic(vec![
+            Move::new("Rock", 'A', 'V'),
+            Move::new("Spock", 'B', 'W'),
+            Move::new("Paper", 'C', 'X'),
+            Move::new("Lizard", 'D', 'Y'),
+            Move::new("Scissors", 'E', 'Z'),
+        ]).expect("Rock-Paper-Scissors-Lizard-Spock is a balanced game")
+    }
+
+    fn from_matrix(moves: Vec<Move>, beats: Vec<Vec<bool>>) -> Result<Game, anyhow::Error>
+    {
+        if moves.is_empty() || moves.len() > u8::MAX as usize
+        {
+            return Err(anyhow::Error::msg(format!("a game can't have {} moves.", moves.len())));
+        }
+
+        for (index, m) in moves.iter().enumerate()
+        {
+            let duplicate = moves[..index].iter()
+                .any(|other| other.name == m.name || other.their_symbol == m.their_symbol || other.your_symbol == m.your_symbol);
+            if duplicate
+            {
+                return Err(anyhow::Error::msg(format!("move '{}' reuses a name or symbol of another move.", m.name)));
+            }
+        }
+
+        for winner in 0..moves.len()
+        {
+            if beats[winner][winner]
+            {
+                return Err(anyhow::Error::msg(format!("'{}' can't beat itself.", moves[winner].name)));
+            }
+
+            for loser in 0..moves.len()
+            {
+                if winner != loser && beats[winner][loser] == beats[loser][winner]
+                {
+                    return Err(anyhow::Error::msg(format!(
+                        "exactly one of '{}' and '{}' has to beat the other.", moves[winner].name, moves[loser].name)));
+                }
+            }
+
+            let num_beaten = beats[winner].iter().filter(|beaten| **beaten).count();
+            if num_beaten * 2 != moves.len() - 1
+            {
+                return Err(anyhow::Error::msg(format!(
+                    "'{}' beats {num_beaten} of {} moves, the game isn't balanced.", moves[winner].name, moves.len())));
+            }
+        }
+
+        Ok(Game { moves, beats })
+    }
+
+    pub fn num_moves(&self) -> usize { self.moves.len() }
+
+    pub fn choices(&self) -> impl Iterator<Item = Choice>
+    {
+        (0..self.moves.len() as u8).map(Choice::new)
+    }
+
+    pub fn get_move(&self, choice: Choice) -> &Move
+    {
+        &self.moves[choice.get_index() as usize]
+    }
+
+    pub fn parse_their_choice(&self, symbol: char) -> Option<Choice>
+    {
+        self.moves.iter().position(|m| m.their_symbol == symbol).map(|index| Choice::new(index as u8))
+    }
+
+    pub fn parse_your_choice(&self, symbol: char) -> Option<Choice>
+    {
+        self.moves.iter().position(|m| m.your_symbol == symbol).map(|index| Choice::new(index as u8))
+    }
+
+    pub fn against(&self, your: Choice, their: Choice) -> RoundResult
+    {
+        let your = your.get_index() as usize;
+        let their = their.get_index() as usize;
+        match (your == their, self.beats[your][their])
+        {
+            (true, _) => RoundResult::Draw,
+            (false, true) => RoundResult::Win,
+            (false, false) => RoundResult::Lose,
+        }
+    }
+
+    // With more than three moves several choices give the same result, the first one is picked.
+    pub fn get_choice_for_result(&self, their: Choice, result: RoundResult) -> Choice
+    {
+        self.choices()
+            .find(|your| self.against(*your, their) == result)
+            .expect("a balanced game has a choice for every result")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn lizard_spock()
+    {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let choice = |name: &str| game.choices().find(|c| game.get_move(*c).get_name() == name).unwrap();
+
+        assert_eq!(game.against(choice("Rock"), choice("Lizard")), RoundResult::Win);
+        assert_eq!(game.against(choice("Lizard"), choice("Spock")), RoundResult::Win);
+        assert_eq!(game.against(choice("Spock"), choice("Scissors")), RoundResult::Win);
+        assert_eq!(game.against(choice("Scissors"), choice("Lizard")), RoundResult::Win);
+        assert_eq!(game.against(choice("Lizard"), choice("Paper")), RoundResult::Win);
+        assert_eq!(game.against(choice("Paper"), choice("Spock")), RoundResult::Win);
+        assert_eq!(game.against(choice("Spock"), choice("Rock")), RoundResult::Win);
+        assert_eq!(game.against(choice("Rock"), choice("Paper")), RoundResult::Lose);
+
+        for their in game.choices()
+        {
+            for result in [RoundResult::Win, RoundResult::Draw, RoundResult::Lose]
+            {
+                assert_eq!(game.against(game.get_choice_for_result(their, result), their), result);
+            }
+        }
+    }
+
+    #[test]
+    fn explicit_dominance()
+    {
+        let moves = || vec![Move::new("Rock", 'A', 'X'), Move::new("Paper", 'B', 'Y'), Move::new("Scissors", 'C', 'Z')];
+
+        let game = Game::new(moves(), &[("Rock", "Scissors"), ("Scissors", "Paper"), ("Paper", "Rock")]).unwrap();
+        assert_eq!(game.against(Choice::ROCK, Choice::SCISSORS), RoundResult::Win);
+        assert_eq!(game.against(Choice::ROCK, Choice::PAPER), RoundResult::Lose);
+
+        assert!(Game::new(moves(), &[("Rock", "Scissors"), ("Rock", "Paper"), ("Paper", "Scissors")]).is_err());
+        assert!(Game::new(moves(), &[("Rock", "Scissors"), ("Scissors", "Paper")]).is_err());
+        assert!(Game::new(moves(), &[("Rock", "Lizard")]).is_err());
+        assert!(Game::cyclic(vec![Move::new("Rock", 'A', 'X'), Move::new("Paper", 'B', 'Y')]).is_err());
+    }
+}
@@ -1,28 +1,33 @@
-use super::round::{Round, Choice, Their, Your, RoundResult};
+use super::game::Game;
+use super::round::{Round, Their, Your, RoundResult};
 
-pub fn parse_round(round_line: &str) -> Result<Round, anyhow::Error>
+pub const RESULT_SYMBOLS: [(char, RoundResult); 3] =
+    [('X', RoundResult::Lose), ('Y', RoundResult::Draw), ('Z', RoundResult::Win)];
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Interpretation
+{
+    // first puzzle: the second column is your choice
+    ChoicePair,
+    // second puzzle: the second column is the result you want
+    DesiredOutcome,
+}
+
+pub fn parse_round(round_line: &str, interpretation: Interpretation, game: &Game) -> Result<Round, anyhow::Error>
 {
-    // first puzzle
-    // let to_round =
-    //     |(their, your)|
-    //     parse_choice(their)
-    //     .and_then(
-    //         |their|
-    //         parse_choice(your)
-    //             .map(|your| Round::new(Their(their), Your(your))
-    //         )
-    //     );
-
-    // second puzzle
     let to_round =
-        |(their, result)|
-        parse_choice(their)
+        |(their, second): (&str, &str)|
+        parse_choice(their, |symbol| game.parse_their_choice(symbol))
         .and_then(
             |their|
-            parse_wanted_result(result)
-                .map(|result| their.get_choice_for_result(result))
-                .and_then(|your| Ok(Round::new(Their(their), Your(your)))
-            )
+            match interpretation
+            {
+                Interpretation::ChoicePair => parse_choice(second, |symbol| game.parse_your_choice(symbol)),
+                Interpretation::DesiredOutcome =>
+                    parse_wanted_result(second)
+                        .map(|result| game.get_choice_for_result(their, result)),
+            }
+            .map(|your| Round::new(Their(their), Your(your)))
         );
 
     round_line
@@ -33,24 +38,44 @@ pub fn parse_round(round_line: &str) -> Result<Round, anyhow::Error>
         )
 }
 
-fn parse_choice(choice_str: &str) -> Result<Choice, anyhow::Error>
+// Ignores anything after the first column.
+pub fn parse_their_column(round_line: &str, game: &Game) -> Result<Their, anyhow::Error>
+{
+    let their = round_line.split_once(' ').map_or(round_line, |(their, _)| their);
+    parse_choice(their, |symbol| game.parse_their_choice(symbol)).map(Their)
+}
+
+// Keeps the second column as the raw symbol, for when its meaning isn't known yet.
+pub fn parse_encrypted_round(round_line: &str, game: &Game) -> Result<(Their, char), anyhow::Error>
 {
-    match choice_str.trim()
+    let (their, second) = round_line.split_once(' ')
+        .ok_or_else(|| anyhow::Error::msg(format!("couldn't split line '{round_line}'")))?;
+
+    let their = parse_choice(their, |symbol| game.parse_their_choice(symbol))?;
+    let symbol = parse_choice(second, |symbol| game.parse_your_choice(symbol).map(|_| symbol)
+        .or_else(|| RESULT_SYMBOLS.iter().find(|(result_symbol, _)| *result_symbol == symbol).map(|_| symbol)))?;
+
+    Ok((Their(their), symbol))
+}
+
+fn parse_choice<T>(choice_str: &str, parse_symbol: impl Fn(char) -> Option<T>) -> Result<T, anyhow::Error>
+{
+    let mut chars = choice_str.trim().chars();
+    match (chars.next(), chars.next())
     {
-        "A" | "X" => Ok(Choice::Rock),
-        "B" | "Y" => Ok(Choice::Paper),
-        "C" | "Z" => Ok(Choice::Scissors),
-        _ => Err(anyhow::Error::msg(format!("'{choice_str}' is not a valid choice."))),
+        (Some(symbol), None) => parse_symbol(symbol),
+        _ => None,
     }
+    .ok_or_else(|| anyhow::Error::msg(format!("'{choice_str}' is not a valid choice.")))
 }
 
 fn parse_wanted_result(result_str: &str) -> Result<RoundResult, anyhow::Error>
 {
-    match result_str.trim()
+    let mut chars = result_str.trim().chars();
+    match (chars.next(), chars.next())
     {
-        "X" => Ok(RoundResult::Lose),
-        "Y" => Ok(RoundResult::Draw),
-        "Z" => Ok(RoundResult::Win),
-        _ => Err(anyhow::Error::msg(format!("'{result_str}' is not a valid result."))),
+        (Some(symbol), None) => RESULT_SYMBOLS.iter().find(|(result_symbol, _)| *result_symbol == symbol).map(|(_, result)| *result),
+        _ => None,
     }
-}
\ No newline at end of file
+    .ok_or_else(|| anyhow::Error::msg(format!("'{result_str}' is not a valid result.")))
+}
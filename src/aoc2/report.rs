@@ -0,0 +1,118 @@
+use super::game::Game;
+use super::round::{Choice, Round, RoundResult};
+use super::scoring::Scoring;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct RoundReport
+{
+    pub line_number: usize,
+    pub their: Choice,
+    pub your: Choice,
+    pub result: RoundResult,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+    pub bonus_points: i32,
+    pub running_total: i32,
+}
+
+#[derive(Debug,Clone,Copy,Default,PartialEq)]
+pub struct OutcomeCounts
+{
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl RoundReport
+{
+    pub fn get_points(&self) -> i32
+    {
+        self.shape_points + self.outcome_points + self.bonus_points
+    }
+}
+
+// Expects one round per guide line, in order.
+pub fn report_rounds(rounds: &[Round], game: &Game, scoring: &Scoring) -> Vec<RoundReport>
+{
+    let bonuses = scoring.calc_streak_bonuses(rounds, game);
+
+    rounds.iter().zip(bonuses).enumerate()
+        .scan(0, |running_total, (index, (round, bonus_points))|
+        {
+            let result = round.get_result(game);
+            let shape_points = scoring.get_shape_score(round.get_your());
+            let outcome_points = scoring.get_outcome_score(result);
+            *running_total += shape_points + outcome_points + bonus_points;
+
+            Some(RoundReport
+            {
+                line_number: index + 1,
+                their: round.get_their(),
+                your: round.get_your(),
+                result,
+                shape_points,
+                outcome_points,
+                bonus_points,
+                running_total: *running_total,
+            })
+        })
+        .collect()
+}
+
+// Indexed by your choice.
+pub fn count_outcomes(rounds: &[Round], game: &Game) -> Vec<OutcomeCounts>
+{
+    rounds.iter()
+        .fold(
+            vec![OutcomeCounts::default(); game.num_moves()],
+            |mut counts, round|
+            {
+                let count = &mut counts[round.get_your().get_index() as usize];
+                match round.get_result(game)
+                {
+                    RoundResult::Win => count.wins += 1,
+                    RoundResult::Draw => count.draws += 1,
+                    RoundResult::Lose => count.losses += 1,
+                }
+                counts
+            }
+        )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::{parse_round, Interpretation};
+    use super::super::scoring::parse_scoring;
+
+    #[test]
+    fn breakdown()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = parse_scoring("streak 2 10", &game).unwrap();
+        let rounds = ["A Y", "B Z", "C Z", "A X"].iter()
+            .map(|line| parse_round(line, Interpretation::ChoicePair, &game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let reports = report_rounds(&rounds, &game, &scoring);
+        assert_eq!(reports.iter().map(|report| report.running_total).collect::<Vec<_>>(), vec![8, 8 + 19, 27 + 6, 33 + 4]);
+        assert_eq!(reports[1], RoundReport
+        {
+            line_number: 2,
+            their: Choice::PAPER,
+            your: Choice::SCISSORS,
+            result: RoundResult::Win,
+            shape_points: 3,
+            outcome_points: 6,
+            bonus_points: 10,
+            running_total: 27,
+        });
+        assert_eq!(reports.last().unwrap().running_total, scoring.calc_total_score(&rounds, &game));
+
+        let counts = count_outcomes(&rounds, &game);
+        assert_eq!(counts[Choice::ROCK.get_index() as usize], OutcomeCounts { wins: 0, draws: 1, losses: 0 });
+        assert_eq!(counts[Choice::SCISSORS.get_index() as usize], OutcomeCounts { wins: 1, draws: 1, losses: 0 });
+    }
+}
@@ -1,15 +1,18 @@
+use std::fmt;
 
-pub fn calc_round_score(round: &Round) -> i32
+use super::game::Game;
+use super::parse::RESULT_SYMBOLS;
+use super::scoring::Scoring;
+
+pub fn calc_round_score(round: &Round, game: &Game, scoring: &Scoring) -> i32
 {
-    round.your.0.get_score() + round.get_score()
+    scoring.get_shape_score(round.your.0) + scoring.get_outcome_score(round.get_result(game))
 }
 
-#[derive(Debug,Clone,Copy,PartialEq)]
-pub enum Choice
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub struct Choice
 {
-    Rock,
-    Paper,
-    Scissors,
+    index: u8,
 }
 
 #[derive(Debug,Clone,Copy)]
@@ -27,81 +30,33 @@ pub struct Round
 
 impl Choice
 {
-    fn get_score(&self) -> i32
-    {
-        match *self
-        {
-            Choice::Rock => 1,
-            Choice::Paper => 2,
-            Choice::Scissors => 3,
-        }
-    }
+    // The moves of the 2022 rules, see `Game::rock_paper_scissors`.
+    pub const ROCK: Choice = Choice { index: 0 };
+    pub const PAPER: Choice = Choice { index: 1 };
+    pub const SCISSORS: Choice = Choice { index: 2 };
 
-    fn get_winning_choice(&self) -> Choice
+    pub fn new(index: u8) -> Choice
     {
-        match *self
-        {
-            Choice::Rock => Choice::Paper,
-            Choice::Paper => Choice::Scissors,
-            Choice::Scissors => Choice::Rock,
-        }
+        Choice { index }
     }
 
-    fn get_losing_choice(&self) -> Choice
-    {
-        match *self
-        {
-            Choice::Paper => Choice::Rock,
-            Choice::Scissors => Choice::Paper,
-            Choice::Rock => Choice::Scissors,
-        }
-    }
-
-    fn get_drawing_choice(&self) -> Choice
-    {
-        *self
-    }
-
-    fn against(&self, choice: Choice) -> RoundResult
-    {
-        match choice.get_drawing_choice().eq(&self)
-        {
-            true => RoundResult::Draw,
-            false =>
-            match choice.get_winning_choice().eq(self)
-            {
-                true => RoundResult::Win,
-                false => RoundResult::Lose,
-            }
-        }
-    }
-
-    pub fn get_choice_for_result(&self, result: RoundResult) -> Choice
-    {
-        match result
-        {
-            RoundResult::Draw => self.get_drawing_choice(),
-            RoundResult::Win => self.get_winning_choice(),
-            RoundResult::Lose => self.get_losing_choice(),
-        }
-    }
+    pub fn get_index(&self) -> u8 { self.index }
 }
 
 impl Round
 {
     pub fn new(their: Their, your: Your) -> Round
     {
-        Round {their: their, your: your}
+        Round {their, your}
     }
 
-    fn get_score(&self) -> i32
+    pub fn get_their(&self) -> Choice { self.their.0 }
+
+    pub fn get_your(&self) -> Choice { self.your.0 }
+
+    pub fn get_result(&self, game: &Game) -> RoundResult
     {
-        match self.your.0.against(self.their.0)
-        {
-            RoundResult::Lose => 0,
-            RoundResult::Draw => 3,
-            RoundResult::Win => 6,
-        }
+        game.against(self.your.0, self.their.0)
     }
 }
 
@@ -113,6 +68,25 @@ pub enum RoundResult
     Lose,
 }
 
+impl RoundResult
+{
+    pub fn get_symbol(&self) -> char
+    {
+        RESULT_SYMBOLS.iter()
+            .find(|(_, result)| result == self)
+            .map(|(symbol, _)| *symbol)
+            .expect("every result has a symbol")
+    }
+}
+
+impl fmt::Display for RoundResult
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.get_symbol())
+    }
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -120,15 +94,26 @@ mod tests
 
     #[test]
     fn test_choices()
-    {    
-        assert_eq!(Choice::Rock.against(Choice::Rock), RoundResult::Draw);
-        assert_eq!(Choice::Scissors.against(Choice::Scissors), RoundResult::Draw);
-        assert_eq!(Choice::Paper.against(Choice::Paper), RoundResult::Draw);
-        assert_eq!(Choice::Rock.against(Choice::Paper), RoundResult::Lose);
-        assert_eq!(Choice::Paper.against(Choice::Scissors), RoundResult::Lose);
-        assert_eq!(Choice::Scissors.against(Choice::Rock), RoundResult::Lose);
-        assert_eq!(Choice::Rock.against(Choice::Scissors), RoundResult::Win);
-        assert_eq!(Choice::Scissors.against(Choice::Paper), RoundResult::Win);
-        assert_eq!(Choice::Paper.against(Choice::Rock), RoundResult::Win);
+    {
+        let game = Game::rock_paper_scissors();
+        assert_eq!(game.against(Choice::ROCK, Choice::ROCK), RoundResult::Draw);
+        assert_eq!(game.against(Choice::SCISSORS, Choice::SCISSORS), RoundResult::Draw);
+        assert_eq!(game.against(Choice::PAPER, Choice::PAPER), RoundResult::Draw);
+        assert_eq!(game.against(Choice::ROCK, Choice::PAPER), RoundResult::Lose);
+        assert_eq!(game.against(Choice::PAPER, Choice::SCISSORS), RoundResult::Lose);
+        assert_eq!(game.against(Choice::SCISSORS, Choice::ROCK), RoundResult::Lose);
+        assert_eq!(game.against(Choice::ROCK, Choice::SCISSORS), RoundResult::Win);
+        assert_eq!(game.against(Choice::SCISSORS, Choice::PAPER), RoundResult::Win);
+        assert_eq!(game.against(Choice::PAPER, Choice::ROCK), RoundResult::Win);
+    }
+
+    #[test]
+    fn round_score()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        assert_eq!(calc_round_score(&Round::new(Their(Choice::ROCK), Your(Choice::PAPER)), &game, &scoring), 8);
+        assert_eq!(calc_round_score(&Round::new(Their(Choice::PAPER), Your(Choice::ROCK)), &game, &scoring), 1);
+        assert_eq!(calc_round_score(&Round::new(Their(Choice::SCISSORS), Your(Choice::SCISSORS)), &game, &scoring), 6);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,187 @@
+use super::game::Game;
+use super::round::{self, Choice, Round, RoundResult};
+
+// Scoring table, starting from the 2022 rules and overridden line by line from a file:
+//
+//   shape <move name> <points>
+//   outcome <lose|draw|win> <points>
+//   streak <wins in a row> <bonus points for every win from then on>
+//
+// Empty lines and lines starting with '#' are ignored.
+#[derive(Debug,Clone,PartialEq)]
+pub struct Scoring
+{
+    shape_scores: Vec<i32>,
+    lose_score: i32,
+    draw_score: i32,
+    win_score: i32,
+    streak_bonuses: Vec<StreakBonus>,
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct StreakBonus
+{
+    wins: usize,
+    bonus: i32,
+}
+
+impl Scoring
+{
+    pub fn standard(game: &Game) -> Scoring
+    {
+        Scoring
+        {
+            shape_scores: game.choices().map(|choice| choice.get_index() as i32 + 1).collect(),
+            lose_score: 0,
+            draw_score: 3,
+            win_score: 6,
+            streak_bonuses: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &str, game: &Game) -> Result<Scoring, anyhow::Error>
+    {
+        let scoring_str = std::fs::read_to_string(path)?;
+        parse_scoring(&scoring_str, game)
+            .map_err(|e| anyhow::Error::msg(format!("couldn't load scoring rules '{path}': {e}")))
+    }
+
+    pub fn get_shape_score(&self, choice: Choice) -> i32
+    {
+        self.shape_scores[choice.get_index() as usize]
+    }
+
+    pub fn get_outcome_score(&self, result: RoundResult) -> i32
+    {
+        match result
+        {
+            RoundResult::Lose => self.lose_score,
+            RoundResult::Draw => self.draw_score,
+            RoundResult::Win => self.win_score,
+        }
+    }
+
+    pub fn has_streak_bonuses(&self) -> bool { !self.streak_bonuses.is_empty() }
+
+    pub fn get_streak_bonus(&self, wins_in_a_row: usize) -> i32
+    {
+        self.streak_bonuses.iter()
+            .filter(|streak| wins_in_a_row >= streak.wins)
+            .map(|streak| streak.bonus)
+            .sum()
+    }
+
+    // The bonus every round earns for extending a winning streak, in order.
+    pub fn calc_streak_bonuses(&self, rounds: &[Round], game: &Game) -> Vec<i32>
+    {
+        rounds.iter()
+            .scan(0, |wins_in_a_row, round|
+            {
+                *wins_in_a_row = match round.get_result(game)
+                {
+                    RoundResult::Win => *wins_in_a_row + 1,
+                    _ => 0,
+                };
+                Some(self.get_streak_bonus(*wins_in_a_row))
+            })
+            .collect()
+    }
+
+    // Includes the streak bonuses, which depend on the order of the rounds.
+    pub fn calc_total_score(&self, rounds: &[Round], game: &Game) -> i32
+    {
+        let round_scores = rounds.iter().map(|round| round::calc_round_score(round, game, self)).sum::<i32>();
+        round_scores + self.calc_streak_bonuses(rounds, game).iter().sum::<i32>()
+    }
+}
+
+pub fn parse_scoring(scoring_str: &str, game: &Game) -> Result<Scoring, anyhow::Error>
+{
+    let mut scoring = Scoring::standard(game);
+
+    for (line_index, line) in scoring_str.lines().enumerate()
+    {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#')
+        {
+            continue;
+        }
+
+        parse_scoring_line(&mut scoring, line, game)
+            .map_err(|e| anyhow::Error::msg(format!("line {}: {e}", line_index + 1)))?;
+    }
+
+    Ok(scoring)
+}
+
+fn parse_scoring_line(scoring: &mut Scoring, line: &str, game: &Game) -> Result<(), anyhow::Error>
+{
+    let args = line.split_whitespace().collect::<Vec<_>>();
+    let (kind, key, points) = match args[..]
+    {
+        [kind, key, points] => (kind, key, points.parse::<i32>()?),
+        _ => return Err(anyhow::Error::msg(format!("'{line}' should have the form '<kind> <key> <points>'"))),
+    };
+
+    match kind
+    {
+        "shape" =>
+        {
+            let choice = game.choices()
+                .find(|choice| game.get_move(*choice).get_name() == key)
+                .ok_or_else(|| anyhow::Error::msg(format!("'{key}' is not a move of the game")))?;
+            scoring.shape_scores[choice.get_index() as usize] = points;
+        },
+        "outcome" =>
+        {
+            match key
+            {
+                "lose" => scoring.lose_score = points,
+                "draw" => scoring.draw_score = points,
+                "win" => scoring.win_score = points,
+                _ => return Err(anyhow::Error::msg(format!("'{key}' is not an outcome"))),
+            }
+        },
+        "streak" =>
+        {
+            let wins = key.parse::<usize>()?;
+            if wins == 0
+            {
+                return Err(anyhow::Error::msg("a streak needs at least one win"));
+            }
+            scoring.streak_bonuses.push(StreakBonus { wins, bonus: points });
+        },
+        _ => return Err(anyhow::Error::msg(format!("'{kind}' is not a scoring rule"))),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::round::{Their, Your};
+
+    #[test]
+    fn variants()
+    {
+        let game = Game::rock_paper_scissors();
+        let rounds = [
+            Round::new(Their(Choice::ROCK), Your(Choice::PAPER)),
+            Round::new(Their(Choice::PAPER), Your(Choice::SCISSORS)),
+            Round::new(Their(Choice::SCISSORS), Your(Choice::ROCK)),
+            Round::new(Their(Choice::ROCK), Your(Choice::SCISSORS)),
+        ];
+
+        assert_eq!(Scoring::standard(&game).calc_total_score(&rounds, &game), 8 + 9 + 7 + 3);
+
+        let scoring = parse_scoring("# losing hurts\noutcome lose -5\nshape Rock 10\n\nstreak 2 100\nstreak 3 1000\n", &game).unwrap();
+        assert_eq!(scoring.calc_total_score(&rounds, &game), 8 + (9 + 100) + (16 + 1100) + (3 - 5));
+
+        assert!(parse_scoring("shape Lizard 4", &game).is_err());
+        assert!(parse_scoring("outcome tie 4", &game).is_err());
+        assert!(parse_scoring("streak 0 4", &game).is_err());
+        assert!(parse_scoring("shape Rock", &game).is_err());
+    }
+}
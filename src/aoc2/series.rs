@@ -0,0 +1,193 @@
+use super::game::Game;
+use super::round::{Round, RoundResult};
+use super::scoring::Scoring;
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum MatchFormat
+{
+    // Majority of the given number of decisive rounds, draws are replayed.
+    BestOf(usize),
+    FirstTo(usize),
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Side
+{
+    You,
+    Them,
+}
+
+// Every match you win that brings your run of won matches to `matches` or more earns `bonus`.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct MatchStreakBonus
+{
+    pub matches: usize,
+    pub bonus: i32,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct SeriesRules
+{
+    pub format: MatchFormat,
+    pub streak_bonuses: Vec<MatchStreakBonus>,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct MatchOutcome
+{
+    pub first_line: usize,
+    pub num_rounds: usize,
+    pub your_wins: usize,
+    pub their_wins: usize,
+    pub draws: usize,
+    // None when the guide ends before the match is decided.
+    pub winner: Option<Side>,
+    pub score: i32,
+    pub streak_bonus: i32,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Series
+{
+    pub matches: Vec<MatchOutcome>,
+    pub matches_won: usize,
+    pub matches_lost: usize,
+    pub longest_winning_streak: usize,
+    pub total_score: i32,
+}
+
+impl MatchFormat
+{
+    pub fn get_wins_needed(&self) -> Result<usize, anyhow::Error>
+    {
+        match *self
+        {
+            MatchFormat::BestOf(rounds) if rounds % 2 == 1 => Ok(rounds / 2 + 1),
+            MatchFormat::FirstTo(wins) if wins > 0 => Ok(wins),
+            _ => Err(anyhow::Error::msg(format!("{self:?} isn't a valid match format."))),
+        }
+    }
+}
+
+impl SeriesRules
+{
+    pub fn new(format: MatchFormat) -> SeriesRules
+    {
+        SeriesRules { format, streak_bonuses: Vec::new() }
+    }
+
+    fn get_streak_bonus(&self, matches_in_a_row: usize) -> i32
+    {
+        self.streak_bonuses.iter()
+            .filter(|streak| matches_in_a_row >= streak.matches)
+            .map(|streak| streak.bonus)
+            .sum()
+    }
+}
+
+// Splits the rounds of a guide into consecutive matches. The round scoring, including its streak
+// bonuses, starts over with every match.
+pub fn play_series(rounds: &[Round], game: &Game, scoring: &Scoring, rules: &SeriesRules) -> Result<Series, anyhow::Error>
+{
+    let wins_needed = rules.format.get_wins_needed()?;
+
+    let mut matches = Vec::new();
+    let mut match_start = 0;
+    let mut wins = (0, 0);
+
+    for (index, round) in rounds.iter().enumerate()
+    {
+        match round.get_result(game)
+        {
+            RoundResult::Win => wins.0 += 1,
+            RoundResult::Lose => wins.1 += 1,
+            RoundResult::Draw => (),
+        }
+
+        let winner = match wins
+        {
+            (yours, _) if yours == wins_needed => Some(Side::You),
+            (_, theirs) if theirs == wins_needed => Some(Side::Them),
+            _ => None,
+        };
+
+        if winner.is_some() || index + 1 == rounds.len()
+        {
+            let match_rounds = &rounds[match_start..=index];
+            matches.push(MatchOutcome
+            {
+                first_line: match_start + 1,
+                num_rounds: match_rounds.len(),
+                your_wins: wins.0,
+                their_wins: wins.1,
+                draws: match_rounds.len() - wins.0 - wins.1,
+                winner,
+                score: scoring.calc_total_score(match_rounds, game),
+                streak_bonus: 0,
+            });
+
+            match_start = index + 1;
+            wins = (0, 0);
+        }
+    }
+
+    let mut matches_in_a_row = 0;
+    let mut longest_winning_streak = 0;
+    for outcome in matches.iter_mut()
+    {
+        matches_in_a_row = match outcome.winner
+        {
+            Some(Side::You) => matches_in_a_row + 1,
+            _ => 0,
+        };
+        longest_winning_streak = longest_winning_streak.max(matches_in_a_row);
+
+        if outcome.winner == Some(Side::You)
+        {
+            outcome.streak_bonus = rules.get_streak_bonus(matches_in_a_row);
+        }
+    }
+
+    Ok(Series
+    {
+        matches_won: matches.iter().filter(|outcome| outcome.winner == Some(Side::You)).count(),
+        matches_lost: matches.iter().filter(|outcome| outcome.winner == Some(Side::Them)).count(),
+        longest_winning_streak,
+        total_score: matches.iter().map(|outcome| outcome.score + outcome.streak_bonus).sum(),
+        matches,
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::parse::{parse_round, Interpretation};
+
+    #[test]
+    fn best_of_three()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        // win, draw, win | lose, lose | win, lose, win | win
+        let rounds = ["A Y", "A X", "B Z", "A Z", "B X", "C X", "C Y", "B Z", "A Y"].iter()
+            .map(|line| parse_round(line, Interpretation::ChoicePair, &game))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut rules = SeriesRules::new(MatchFormat::BestOf(3));
+        rules.streak_bonuses.push(MatchStreakBonus { matches: 1, bonus: 100 });
+        let series = play_series(&rounds, &game, &scoring, &rules).unwrap();
+
+        assert_eq!(series.matches.iter().map(|outcome| outcome.first_line).collect::<Vec<_>>(), vec![1, 4, 6, 9]);
+        assert_eq!(series.matches.iter().map(|outcome| outcome.winner).collect::<Vec<_>>(),
+            vec![Some(Side::You), Some(Side::Them), Some(Side::You), None]);
+        assert_eq!(series.matches[0].draws, 1);
+        assert_eq!(series.matches[0].streak_bonus, 100);
+        assert_eq!((series.matches_won, series.matches_lost, series.longest_winning_streak), (2, 1, 1));
+        assert_eq!(series.total_score, scoring.calc_total_score(&rounds, &game) + 200);
+
+        assert!(play_series(&rounds, &game, &scoring, &SeriesRules::new(MatchFormat::BestOf(4))).is_err());
+        assert_eq!(play_series(&rounds, &game, &scoring, &SeriesRules::new(MatchFormat::FirstTo(1))).unwrap().matches.len(), 8);
+    }
+}
@@ -0,0 +1,340 @@
+use super::game::Game;
+use super::round::{Choice, Round, RoundResult, Their, Your};
+use super::scoring::Scoring;
+
+// SplitMix64, so simulations are reproducible from a seed.
+#[derive(Debug,Clone)]
+pub struct Rng
+{
+    state: u64,
+}
+
+impl Rng
+{
+    pub fn new(seed: u64) -> Rng
+    {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64
+    {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_below(&mut self, bound: usize) -> usize
+    {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+pub trait Strategy
+{
+    fn get_name(&self, game: &Game) -> String;
+
+    // `opponent_history` holds every choice the opponent made so far, oldest first.
+    fn choose(&mut self, opponent_history: &[Choice], game: &Game, rng: &mut Rng) -> Choice;
+
+    fn reset(&mut self) {}
+
+    fn clone_box(&self) -> Box<dyn Strategy>;
+}
+
+#[derive(Clone)]
+pub struct Always(pub Choice);
+
+#[derive(Clone)]
+pub struct RandomChoice;
+
+#[derive(Clone)]
+pub struct CopyLast;
+
+#[derive(Clone)]
+pub struct BeatLast;
+
+// Beats whatever the opponent played most often so far.
+#[derive(Clone)]
+pub struct FrequencyCounter;
+
+// Plays the moves of a strategy guide column in order, starting over when it runs out.
+#[derive(Clone)]
+pub struct Guide
+{
+    choices: Vec<Choice>,
+    next: usize,
+}
+
+impl Guide
+{
+    pub fn new(choices: Vec<Choice>) -> Result<Guide, anyhow::Error>
+    {
+        match choices.is_empty()
+        {
+            true => Err(anyhow::Error::msg("a guide strategy needs at least one choice.")),
+            false => Ok(Guide { choices, next: 0 }),
+        }
+    }
+}
+
+impl Strategy for Always
+{
+    fn get_name(&self, game: &Game) -> String { format!("always {}", game.get_move(self.0).get_name()) }
+
+    fn choose(&mut self, _: &[Choice], _: &Game, _: &mut Rng) -> Choice { self.0 }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+}
+
+impl Strategy for RandomChoice
+{
+    fn get_name(&self, _: &Game) -> String { "random".to_string() }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+
+    fn choose(&mut self, _: &[Choice], game: &Game, rng: &mut Rng) -> Choice
+    {
+        Choice::new(rng.next_below(game.num_moves()) as u8)
+    }
+}
+
+impl Strategy for CopyLast
+{
+    fn get_name(&self, _: &Game) -> String { "copy last".to_string() }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+
+    fn choose(&mut self, opponent_history: &[Choice], game: &Game, rng: &mut Rng) -> Choice
+    {
+        match opponent_history.last()
+        {
+            Some(last) => *last,
+            None => RandomChoice.choose(opponent_history, game, rng),
+        }
+    }
+}
+
+impl Strategy for BeatLast
+{
+    fn get_name(&self, _: &Game) -> String { "beat last".to_string() }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+
+    fn choose(&mut self, opponent_history: &[Choice], game: &Game, rng: &mut Rng) -> Choice
+    {
+        match opponent_history.last()
+        {
+            Some(last) => game.get_choice_for_result(*last, RoundResult::Win),
+            None => RandomChoice.choose(opponent_history, game, rng),
+        }
+    }
+}
+
+impl Strategy for FrequencyCounter
+{
+    fn get_name(&self, _: &Game) -> String { "frequency counter".to_string() }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+
+    fn choose(&mut self, opponent_history: &[Choice], game: &Game, rng: &mut Rng) -> Choice
+    {
+        let mut counts = vec![0usize; game.num_moves()];
+        opponent_history.iter().for_each(|choice| counts[choice.get_index() as usize] += 1);
+
+        match counts.iter().enumerate().filter(|(_, count)| **count > 0).max_by_key(|(_, count)| **count)
+        {
+            Some((most_frequent, _)) => game.get_choice_for_result(Choice::new(most_frequent as u8), RoundResult::Win),
+            None => RandomChoice.choose(opponent_history, game, rng),
+        }
+    }
+}
+
+impl Strategy for Guide
+{
+    fn get_name(&self, _: &Game) -> String { "guide".to_string() }
+
+    fn clone_box(&self) -> Box<dyn Strategy> { Box::new(self.clone()) }
+
+    fn choose(&mut self, _: &[Choice], _: &Game, _: &mut Rng) -> Choice
+    {
+        let choice = self.choices[self.next];
+        self.next = (self.next + 1) % self.choices.len();
+        choice
+    }
+
+    fn reset(&mut self)
+    {
+        self.next = 0;
+    }
+}
+
+pub fn builtin_strategies(game: &Game) -> Vec<Box<dyn Strategy>>
+{
+    game.choices()
+        .map(|choice| Box::new(Always(choice)) as Box<dyn Strategy>)
+        .chain([
+            Box::new(RandomChoice) as Box<dyn Strategy>,
+            Box::new(CopyLast),
+            Box::new(BeatLast),
+            Box::new(FrequencyCounter),
+        ])
+        .collect()
+}
+
+// Plays one match and returns the rounds from your point of view.
+pub fn play_match(
+    your: &mut dyn Strategy,
+    their: &mut dyn Strategy,
+    num_rounds: usize,
+    game: &Game,
+    rng: &mut Rng) -> Vec<Round>
+{
+    your.reset();
+    their.reset();
+
+    let mut your_history = Vec::with_capacity(num_rounds);
+    let mut their_history = Vec::with_capacity(num_rounds);
+
+    for _ in 0..num_rounds
+    {
+        let your_choice = your.choose(&their_history, game, rng);
+        let their_choice = their.choose(&your_history, game, rng);
+        your_history.push(your_choice);
+        their_history.push(their_choice);
+    }
+
+    their_history.into_iter().zip(your_history)
+        .map(|(their, your)| Round::new(Their(their), Your(your)))
+        .collect()
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct ScoreStats
+{
+    pub num_matches: usize,
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl ScoreStats
+{
+    pub fn from_scores(scores: &[i32]) -> Option<ScoreStats>
+    {
+        let num_matches = scores.len();
+        let mean = scores.iter().map(|score| *score as f64).sum::<f64>() / num_matches as f64;
+        let variance = scores.iter().map(|score| (*score as f64 - mean).powi(2)).sum::<f64>() / num_matches as f64;
+
+        Some(ScoreStats
+        {
+            num_matches,
+            min: *scores.iter().min()?,
+            max: *scores.iter().max()?,
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Matchup
+{
+    pub your: String,
+    pub their: String,
+    pub your_scores: ScoreStats,
+    pub their_scores: ScoreStats,
+}
+
+// Every strategy plays `num_matches` matches against every strategy, including itself.
+pub fn run_tournament(
+    strategies: &[Box<dyn Strategy>],
+    num_matches: usize,
+    num_rounds: usize,
+    game: &Game,
+    scoring: &Scoring,
+    seed: u64) -> Result<Vec<Matchup>, anyhow::Error>
+{
+    if num_matches == 0
+    {
+        return Err(anyhow::Error::msg("a tournament needs at least one match per pairing."));
+    }
+
+    let mut rng = Rng::new(seed);
+    let mut matchups = Vec::new();
+
+    for your_index in 0..strategies.len()
+    {
+        for their_index in 0..strategies.len()
+        {
+            let mut your = strategies[your_index].clone_box();
+            let mut their = strategies[their_index].clone_box();
+
+            let (your_scores, their_scores): (Vec<_>, Vec<_>) = (0..num_matches)
+                .map(|_|
+                {
+                    let rounds = play_match(your.as_mut(), their.as_mut(), num_rounds, game, &mut rng);
+                    let mirrored = rounds.iter()
+                        .map(|round| Round::new(Their(round.get_your()), Your(round.get_their())))
+                        .collect::<Vec<_>>();
+                    (scoring.calc_total_score(&rounds, game), scoring.calc_total_score(&mirrored, game))
+                })
+                .unzip();
+
+            matchups.push(Matchup
+            {
+                your: your.get_name(game),
+                their: their.get_name(game),
+                your_scores: ScoreStats::from_scores(&your_scores).expect("at least one match was played"),
+                their_scores: ScoreStats::from_scores(&their_scores).expect("at least one match was played"),
+            });
+        }
+    }
+
+    Ok(matchups)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn strategies()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let mut rng = Rng::new(7);
+
+        let rounds = play_match(&mut BeatLast, &mut Always(Choice::ROCK), 10, &game, &mut rng);
+        assert!(rounds[1..].iter().all(|round| round.get_your() == Choice::PAPER));
+
+        let rounds = play_match(&mut FrequencyCounter, &mut Guide::new(vec![Choice::SCISSORS, Choice::SCISSORS, Choice::PAPER]).unwrap(), 6, &game, &mut rng);
+        assert_eq!(rounds.iter().map(|round| round.get_their()).collect::<Vec<_>>(),
+            vec![Choice::SCISSORS, Choice::SCISSORS, Choice::PAPER, Choice::SCISSORS, Choice::SCISSORS, Choice::PAPER]);
+        assert!(rounds[1..].iter().all(|round| round.get_your() == Choice::ROCK));
+
+        let rounds = play_match(&mut CopyLast, &mut Always(Choice::PAPER), 5, &game, &mut rng);
+        assert_eq!(scoring.calc_total_score(&rounds[1..], &game), 4 * (2 + 3));
+    }
+
+    #[test]
+    fn tournament_is_seeded()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let strategies = builtin_strategies(&game);
+
+        let first = run_tournament(&strategies, 20, 50, &game, &scoring, 42).unwrap();
+        let second = run_tournament(&strategies, 20, 50, &game, &scoring, 42).unwrap();
+        assert_eq!(first.len(), strategies.len() * strategies.len());
+        assert!(first.iter().zip(second.iter()).all(|(a, b)| a.your_scores == b.your_scores));
+
+        let rock_vs_paper = first.iter().find(|m| m.your == "always Rock" && m.their == "always Paper").unwrap();
+        assert_eq!(rock_vs_paper.your_scores.mean, 50.0);
+        assert_eq!(rock_vs_paper.their_scores.mean, 50.0 * 8.0);
+        assert_eq!(rock_vs_paper.your_scores.std_dev, 0.0);
+    }
+}
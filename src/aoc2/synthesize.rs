@@ -0,0 +1,169 @@
+use super::game::Game;
+use super::round::{self, Choice, Round, RoundResult, Their, Your};
+use super::scoring::Scoring;
+
+const UNREACHABLE: u8 = u8::MAX;
+// The walk back keeps one byte per round and reachable delta, so large payouts over many rounds
+// are refused rather than exhausting memory.
+const TABLE_LIMIT: usize = 1 << 26;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Synthesis
+{
+    pub choices: Vec<Choice>,
+    pub num_losses: usize,
+}
+
+#[derive(Debug,Clone,Copy)]
+struct RoundOption
+{
+    choice: Choice,
+    delta: usize,
+    is_loss: bool,
+}
+
+// Picks your choice for every round so the guide scores exactly `target`, with as few losses as
+// possible. Returns None when no assignment reaches the target.
+pub fn synthesize(their: &[Their], target: i32, game: &Game, scoring: &Scoring) -> Result<Option<Synthesis>, anyhow::Error>
+{
+    if scoring.has_streak_bonuses()
+    {
+        return Err(anyhow::Error::msg("guides can't be synthesized for scoring rules with streak bonuses."));
+    }
+
+    // Every round's score is split into the lowest score possible in that round plus a delta.
+    let mut min_total = 0i64;
+    let round_options = their.iter()
+        .map(|their|
+        {
+            let scored = game.choices()
+                .map(|your|
+                {
+                    let round = Round::new(*their, Your(your));
+                    (your, round::calc_round_score(&round, game, scoring), round.get_result(game) == RoundResult::Lose)
+                })
+                .collect::<Vec<_>>();
+
+            let min_score = scored.iter().map(|(_, score, _)| *score).min().expect("a game has at least one move");
+            min_total += min_score as i64;
+
+            scored.into_iter()
+                .map(|(choice, score, is_loss)| RoundOption { choice, delta: (score - min_score) as usize, is_loss })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let max_delta_total = round_options.iter()
+        .map(|options| options.iter().map(|option| option.delta).max().unwrap_or(0))
+        .sum::<usize>();
+
+    let remaining = target as i64 - min_total;
+    if remaining < 0 || remaining > max_delta_total as i64
+    {
+        return Ok(None);
+    }
+    let remaining = remaining as usize;
+
+    let table_size = (remaining + 1).saturating_mul(round_options.len());
+    if table_size > TABLE_LIMIT
+    {
+        return Err(anyhow::Error::msg(format!(
+            "synthesizing {} rounds needs a table of {table_size} entries, the limit is {TABLE_LIMIT}.", round_options.len())));
+    }
+
+    // Forward pass over the reachable deltas, keeping the fewest losses for each and the option
+    // that got there, so the assignment can be walked back from the target.
+    let mut losses = vec![usize::MAX; remaining + 1];
+    losses[0] = 0;
+    let mut picks = Vec::with_capacity(round_options.len());
+
+    for options in &round_options
+    {
+        let mut next_losses = vec![usize::MAX; remaining + 1];
+        let mut round_picks = vec![UNREACHABLE; remaining + 1];
+
+        for (sum, sum_losses) in losses.iter().enumerate().filter(|(_, sum_losses)| **sum_losses != usize::MAX)
+        {
+            for (option_index, option) in options.iter().enumerate()
+            {
+                let next_sum = sum + option.delta;
+                let next_sum_losses = sum_losses + option.is_loss as usize;
+                if next_sum <= remaining && next_sum_losses < next_losses[next_sum]
+                {
+                    next_losses[next_sum] = next_sum_losses;
+                    round_picks[next_sum] = option_index as u8;
+                }
+            }
+        }
+
+        losses = next_losses;
+        picks.push(round_picks);
+    }
+
+    if losses[remaining] == usize::MAX
+    {
+        return Ok(None);
+    }
+
+    let mut choices = Vec::with_capacity(round_options.len());
+    let mut sum = remaining;
+    for (options, round_picks) in round_options.iter().zip(picks.iter()).rev()
+    {
+        let option = options[round_picks[sum] as usize];
+        choices.push(option.choice);
+        sum -= option.delta;
+    }
+    choices.reverse();
+
+    Ok(Some(Synthesis { choices, num_losses: losses[remaining] }))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn matches_brute_force()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = Scoring::standard(&game);
+        let their = [Their(Choice::ROCK), Their(Choice::PAPER), Their(Choice::SCISSORS), Their(Choice::ROCK)];
+
+        let mut fewest_losses = std::collections::HashMap::new();
+        for assignment in 0..81
+        {
+            let rounds = their.iter().enumerate()
+                .map(|(index, their)| Round::new(*their, Your(Choice::new((assignment / 3u32.pow(index as u32) % 3) as u8))))
+                .collect::<Vec<_>>();
+            let num_losses = rounds.iter().filter(|round| round.get_result(&game) == RoundResult::Lose).count();
+            let entry = fewest_losses.entry(scoring.calc_total_score(&rounds, &game)).or_insert(num_losses);
+            *entry = (*entry).min(num_losses);
+        }
+
+        for target in -5..50
+        {
+            let synthesis = synthesize(&their, target, &game, &scoring).unwrap();
+            assert_eq!(synthesis.as_ref().map(|synthesis| synthesis.num_losses), fewest_losses.get(&target).copied());
+
+            if let Some(synthesis) = synthesis
+            {
+                let rounds = their.iter().zip(synthesis.choices.iter())
+                    .map(|(their, your)| Round::new(*their, Your(*your)))
+                    .collect::<Vec<_>>();
+                assert_eq!(scoring.calc_total_score(&rounds, &game), target);
+            }
+        }
+    }
+
+    #[test]
+    fn table_limit()
+    {
+        let game = Game::rock_paper_scissors();
+        let scoring = super::super::scoring::parse_scoring("outcome win 1000000", &game).unwrap();
+        let their = vec![Their(Choice::ROCK); 2500];
+
+        assert!(synthesize(&their, 1000000 * 1250, &game, &scoring).is_err());
+        assert!(synthesize(&their[..2], 1000002 + 4, &game, &scoring).unwrap().is_some());
+    }
+}
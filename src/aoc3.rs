@@ -1,11 +1,43 @@
-mod rucksack;
-mod bitset;
+pub mod rucksack;
+pub mod bitset;
+pub mod grouping;
+pub mod alphabet;
+pub mod validate;
+pub mod repair;
+pub mod infer;
+pub mod index;
+pub mod bulk;
 use rucksack::*;
+use alphabet::Alphabet;
+use grouping::{GroupingMode, Grouping};
+use validate::{Validation, Validated};
+
+const NUM_POCKETS: usize = 2;
+const GROUP_SIZE: usize = 3;
 
 pub fn run(input: String) -> Result<String, anyhow::Error>
 {
+    let (sum_of_priorities, _) = solve(&input, Validation::Strict)?;
+    Ok(sum_of_priorities.to_string())
+}
+
+// Like `run`, but rucksacks and groups without exactly one shared item are listed after the sum
+// instead of failing.
+pub fn run_lenient(input: String) -> Result<String, anyhow::Error>
+{
+    let (sum_of_priorities, warnings) = solve(&input, Validation::Lenient)?;
+
+    Ok(std::iter::once(sum_of_priorities.to_string())
+        .chain(warnings.iter().map(|warning| format!("warning: {warning}")))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn solve(input: &str, validation: Validation) -> Result<(u32, Vec<validate::Diagnostic>), anyhow::Error>
+{
+    let alphabet = Alphabet::standard();
     let rucksacks = input.lines()
-        .map(|line| parse_rucksack(line))
+        .map(|line| parse_rucksack(line, NUM_POCKETS, &alphabet))
         .collect::<Result<Vec<_>, _>>()?;
     
     if rucksacks.is_empty() {
@@ -13,26 +45,108 @@ pub fn run(input: String) -> Result<String, anyhow::Error>
     }
 
     // Part 1
-    let _duplicates = rucksacks.iter()
-        .map(|rucksack| Rucksack::find_duplicates_in_pockets(&rucksack))
-        .collect::<Result<Vec<_>, _>>()?
-        .iter().flatten().copied().collect::<Vec<_>>();
+    let misplaced = validate::validate_misplaced_items(&rucksacks, &alphabet, validation)?;
     
     // Part 2
-    if rucksacks.len() % 3 != 0 {
-        return Err(anyhow::Error::msg("There needs to be a multiple of 3 number of rucksacks!"));
-    }
-
-    let duplicates = rucksacks.chunks_exact(3)
-        .map(|rucksacks| Rucksack::find_duplicates_in_rucksacks(rucksacks))
-        .collect::<Result<Vec<_>, _>>()?
-        .iter().flatten().copied().collect::<Vec<_>>();
+    let grouping = grouping::group_rucksacks(rucksacks.len(), GROUP_SIZE, &GroupingMode::Consecutive)?;
+    let badges = find_badges(&rucksacks, &grouping, &alphabet, validation)?;
     
-    let priorities = duplicates.iter()
+    let sum_of_priorities = badges.items.iter()
         .map(|item| item.get_prio())
-        .collect::<Vec<_>>();
+        .sum::<u32>();
+
+    Ok((sum_of_priorities, [misplaced.warnings, badges.warnings].concat()))
+}
+
+// Like `run`, but every line is "<rucksack> <group id>" and the badges come from the id groups.
+pub fn run_with_group_ids(input: String) -> Result<String, anyhow::Error>
+{
+    let alphabet = Alphabet::standard();
+    let (rucksacks, ids): (Vec<_>, Vec<_>) = input.lines()
+        .map(|line|
+            grouping::parse_group_id_line(line)
+                .and_then(|(rucksack, id)| Ok((parse_rucksack(rucksack, NUM_POCKETS, &alphabet)?, id.to_string())))
+        )
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
 
-    let sum_of_priorities = priorities.iter().fold(0u32, |sum, x| sum + (*x) as u32);
+    let grouping = grouping::group_rucksacks(rucksacks.len(), GROUP_SIZE, &GroupingMode::GroupIds(ids))?;
+    let sum_of_priorities = find_badges(&rucksacks, &grouping, &alphabet, Validation::Strict)?.items.iter()
+        .fold(0u32, |sum, item| sum + item.get_prio());
 
     Ok(sum_of_priorities.to_string())
 }
+
+// Like `run`, but the rucksacks may come in any order and the groups are searched for.
+pub fn run_inferred_groups(input: String) -> Result<String, anyhow::Error>
+{
+    let alphabet = Alphabet::standard();
+    let rucksacks = input.lines()
+        .map(|line| parse_rucksack(line, NUM_POCKETS, &alphabet))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let inferred = infer::infer_groups(&rucksacks, GROUP_SIZE)?;
+    let sum_of_priorities = inferred.groups.iter()
+        .map(|group| Rucksack::find_duplicates_in_rucksacks(group.members.iter().map(|member| &rucksacks[*member]), &alphabet))
+        .collect::<Result<Vec<_>, _>>()?
+        .iter().flatten()
+        .map(|item| item.get_prio())
+        .sum::<u32>();
+
+    let lines = |members: &[usize]| members.iter().map(|member| (member + 1).to_string()).collect::<Vec<_>>().join(", ");
+    let mut report = vec![sum_of_priorities.to_string()];
+    report.extend(inferred.groups.iter().map(|group| group.describe()));
+    if !inferred.is_complete()
+    {
+        report.push(format!("ungrouped lines {}", lines(&inferred.ungrouped)));
+    }
+    report.push(match (inferred.is_exhaustive, inferred.is_unique)
+    {
+        (false, _) => "search stopped early, a larger grouping may exist".to_string(),
+        (true, true) => "the grouping is unique".to_string(),
+        (true, false) => "other groupings of the same size exist".to_string(),
+    });
+
+    Ok(report.join("\n"))
+}
+
+// How many rucksacks hold every item and how many copies there are, most common first.
+pub fn run_inventory(input: String) -> Result<String, anyhow::Error>
+{
+    let alphabet = Alphabet::standard();
+    let rucksacks = input.lines()
+        .map(|line| parse_rucksack(line, NUM_POCKETS, &alphabet))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let index = index::InventoryIndex::new(&rucksacks, &alphabet)?;
+    let mut frequencies = index.frequencies();
+    frequencies.sort_by_key(|frequency| std::cmp::Reverse(frequency.rucksacks));
+
+    let common = index.find_common_items(&(0..index.num_rucksacks()).collect::<Vec<_>>())?;
+
+    Ok(frequencies.iter()
+        .map(|frequency| format!("{}: {} rucksacks, {} items", frequency.item.get_symbol(), frequency.rucksacks, frequency.occurrences))
+        .chain(std::iter::once(format!("in every rucksack: {}", common.iter().map(|item| item.get_symbol()).collect::<String>())))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+// The part 1 sum, read through the byte-level bulk parser.
+pub fn run_bulk(input: String) -> Result<String, anyhow::Error>
+{
+    let parser = bulk::BulkParser::new(&Alphabet::standard())?;
+    Ok(parser.sum_duplicate_priorities(input.as_bytes())?.to_string())
+}
+
+// Errors on incomplete groups, naming them, instead of silently leaving them out.
+fn find_badges(rucksacks: &[Rucksack], grouping: &Grouping, alphabet: &Alphabet, validation: Validation) -> Result<Validated, anyhow::Error>
+{
+    if let Some(group) = grouping.incomplete.first()
+    {
+        return Err(anyhow::Error::msg(format!(
+            "The {} has only {} of {} rucksacks!", group.describe(), group.members.len(), grouping.group_size)));
+    }
+
+    validate::validate_badges(rucksacks, grouping, alphabet, validation)
+}
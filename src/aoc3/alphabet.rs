@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use super::bitset::BitSetIndex;
+use super::rucksack::{Item, ITEM_SET_WORDS};
+
+// Maps the symbols that may appear in a rucksack to their bit index and priority. The bit index
+// is the position of the symbol in the alphabet, the priority can be anything.
+#[derive(Debug,Clone)]
+pub struct Alphabet
+{
+    items: Vec<Item>,
+    ascii_lookup: [Option<u16>; 128],
+    lookup: HashMap<char, u16>,
+}
+
+impl Alphabet
+{
+    pub fn new(symbols: &[(char, u32)]) -> Result<Alphabet, anyhow::Error>
+    {
+        let capacity = BitSetIndex::<ITEM_SET_WORDS>::CAPACITY;
+        if symbols.len() > capacity
+        {
+            return Err(anyhow::Error::msg(format!("An alphabet can't have more than {capacity} symbols.")));
+        }
+
+        let mut alphabet = Alphabet { items: Vec::new(), ascii_lookup: [None; 128], lookup: HashMap::new() };
+        for (index, (symbol, prio)) in symbols.iter().enumerate()
+        {
+            if alphabet.lookup.insert(*symbol, index as u16).is_some()
+            {
+                return Err(anyhow::Error::msg(format!("Symbol '{symbol}' is in the alphabet twice.")));
+            }
+
+            if symbol.is_ascii()
+            {
+                alphabet.ascii_lookup[*symbol as usize] = Some(index as u16);
+            }
+
+            alphabet.items.push(Item::new(*symbol, index as u16, *prio));
+        }
+
+        Ok(alphabet)
+    }
+
+    // The priority of every symbol is its position, starting at 1.
+    pub fn from_ordering(symbols: &str) -> Result<Alphabet, anyhow::Error>
+    {
+        Alphabet::new(&symbols.chars().zip(1..).collect::<Vec<_>>())
+    }
+
+    // a-z are 1-26, A-Z are 27-52.
+    pub fn standard() -> Alphabet
+    {
+        Alphabet::from_ordering("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ")
+            .expect("the standard alphabet is valid")
+    }
+
+    pub fn digits() -> Alphabet
+    {
+        Alphabet::from_ordering("0123456789").expect("the digit alphabet is valid")
+    }
+
+    pub fn len(&self) -> usize { self.items.len() }
+
+    pub fn is_empty(&self) -> bool { self.items.is_empty() }
+
+    pub fn get_item(&self, symbol: char) -> Option<Item>
+    {
+        let index = match symbol.is_ascii()
+        {
+            true => self.ascii_lookup[symbol as usize],
+            false => self.lookup.get(&symbol).copied(),
+        };
+
+        index.map(|index| self.items[index as usize])
+    }
+
+    pub fn get_item_by_index(&self, index: usize) -> Option<Item>
+    {
+        self.items.get(index).copied()
+    }
+
+    pub fn items(&self) -> &[Item] { &self.items }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn alphabets() -> Result<(), anyhow::Error>
+    {
+        let digits = Alphabet::digits();
+        assert_eq!(digits.get_item('0').map(|item| item.get_prio()), Some(1));
+        assert_eq!(digits.get_item('9').map(|item| item.get_prio()), Some(10));
+        assert_eq!(digits.get_item('a'), None);
+
+        let weighted = Alphabet::new(&[('♠', 100), ('♥', 5), ('x', 7)])?;
+        assert_eq!(weighted.get_item('♥').map(|item| (item.get_index(), item.get_prio())), Some((1, 5)));
+        assert_eq!(weighted.get_item('x').map(|item| item.get_symbol()), Some('x'));
+
+        assert!(Alphabet::from_ordering("abca").is_err());
+        let too_many = (0..300).map(|i| (char::from_u32(0x4e00 + i).unwrap(), i)).collect::<Vec<_>>();
+        assert!(Alphabet::new(&too_many).is_err());
+
+        Ok(())
+    }
+}
@@ -1,69 +1,181 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
 
+// A fixed size bit set of N * 64 bits. The default of a single u64 covers the rucksack priorities.
 #[derive(Debug,Copy,Clone)]
-pub struct BitSetIndex
+pub struct BitSetIndex<const N: usize = 1>
 {
-    index: u8,
+    index: usize,
 }
 
-#[derive(Debug,Copy,Clone)]
-pub struct BitSet
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub struct BitSet<const N: usize = 1>
 {
-    bits: u64,
+    bits: [u64; N],
 }
 
-impl BitSetIndex
+// Yields the indices of the set bits in ascending order.
+#[derive(Debug,Clone)]
+pub struct SetBits<const N: usize>
 {
-    pub fn new(index: u8) -> Result<BitSetIndex, anyhow::Error>
+    bits: [u64; N],
+    word: usize,
+}
+
+impl<const N: usize> BitSetIndex<N>
+{
+    pub const CAPACITY: usize = N * 64;
+
+    pub fn new(index: usize) -> Result<BitSetIndex<N>, anyhow::Error>
     {
-        match index
+        match index < Self::CAPACITY
         {
-            0..=63 => Ok(BitSetIndex{index: index}),
-            _ => Err(anyhow::Error::msg("index '{index}' has to between 0 and 64")) 
+            true => Ok(BitSetIndex{index}),
+            false => Err(anyhow::Error::msg(format!("index '{index}' has to be between 0 and {}", Self::CAPACITY))),
         }
     }
 
-    pub fn to_u8(&self) -> u8 { self.index }
+    pub fn to_usize(&self) -> usize { self.index }
+
+    fn word(&self) -> usize { self.index / 64 }
+
+    fn mask(&self) -> u64 { 1 << (self.index % 64) }
 }
 
-impl BitSet
+impl BitSet<1>
 {
     pub fn new(bits: u64) -> BitSet
     {
-        BitSet{bits: bits}
+        BitSet{bits: [bits]}
+    }
+}
+
+impl<const N: usize> BitSet<N>
+{
+    pub fn from_words(bits: [u64; N]) -> BitSet<N>
+    {
+        BitSet{bits}
+    }
+
+    pub fn new_empty() -> BitSet<N>
+    {
+        BitSet::from_words([0; N])
+    }
+
+    pub fn set(&mut self, index: &BitSetIndex<N>)
+    {
+        self.bits[index.word()] |= index.mask();
+    }
+
+    pub fn remove(&mut self, index: &BitSetIndex<N>)
+    {
+        self.bits[index.word()] &= !index.mask();
+    }
+
+    pub fn contains(&self, index: &BitSetIndex<N>) -> bool
+    {
+        self.bits[index.word()] & index.mask() != 0
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.bits.iter().all(|word| *word == 0)
     }
 
-    pub fn new_empty() -> BitSet
+    pub fn iter(&self) -> SetBits<N>
     {
-        BitSet::new(0)
+        SetBits{bits: self.bits, word: 0}
     }
 
-    pub fn set(&mut self, index: &BitSetIndex)
+    pub fn is_subset(&self, other: &BitSet<N>) -> bool
     {
-        self.bits |= 1 << index.to_u8();
+        self.bits.iter().zip(other.bits.iter()).all(|(a, b)| a & !b == 0)
     }
 
-    pub fn get_set_bits(&self) -> Vec<u8>
+    pub fn complement(&self) -> BitSet<N>
     {
-        (0..=63).into_iter()
-            .filter(|index| self.bits & (1 << index) != 0)
-            .clone()
-            .collect::<Vec<_>>()
+        BitSet::from_words(self.bits.map(|word| !word))
     }
 
-    pub fn intersect(a: &BitSet, b: &BitSet) -> BitSet
+    pub fn intersect(a: &BitSet<N>, b: &BitSet<N>) -> BitSet<N>
     {
-        BitSet::new(a.bits & b.bits)
+        BitSet::from_words(std::array::from_fn(|word| a.bits[word] & b.bits[word]))
     }
 
-    pub fn extend(a: &BitSet, b: &BitSet) -> BitSet
+    pub fn extend(a: &BitSet<N>, b: &BitSet<N>) -> BitSet<N>
+    {
+        BitSet::from_words(std::array::from_fn(|word| a.bits[word] | b.bits[word]))
+    }
+
+    pub fn difference(a: &BitSet<N>, b: &BitSet<N>) -> BitSet<N>
+    {
+        BitSet::from_words(std::array::from_fn(|word| a.bits[word] & !b.bits[word]))
+    }
+
+    pub fn symmetric_difference(a: &BitSet<N>, b: &BitSet<N>) -> BitSet<N>
+    {
+        BitSet::from_words(std::array::from_fn(|word| a.bits[word] ^ b.bits[word]))
+    }
+}
+
+impl<const N: usize> Iterator for SetBits<N>
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize>
     {
-        BitSet::new(a.bits | b.bits)
+        while self.word < N
+        {
+            let bits = &mut self.bits[self.word];
+            if *bits != 0
+            {
+                let bit = bits.trailing_zeros() as usize;
+                *bits &= *bits - 1;
+                return Some(self.word * 64 + bit);
+            }
+
+            self.word += 1;
+        }
+
+        None
     }
 }
 
-impl FromIterator<BitSetIndex> for BitSet
+impl<const N: usize> BitAnd for BitSet<N>
+{
+    type Output = BitSet<N>;
+
+    fn bitand(self, rhs: BitSet<N>) -> BitSet<N> { BitSet::intersect(&self, &rhs) }
+}
+
+impl<const N: usize> BitOr for BitSet<N>
+{
+    type Output = BitSet<N>;
+
+    fn bitor(self, rhs: BitSet<N>) -> BitSet<N> { BitSet::extend(&self, &rhs) }
+}
+
+impl<const N: usize> BitXor for BitSet<N>
+{
+    type Output = BitSet<N>;
+
+    fn bitxor(self, rhs: BitSet<N>) -> BitSet<N> { BitSet::symmetric_difference(&self, &rhs) }
+}
+
+impl<const N: usize> Not for BitSet<N>
+{
+    type Output = BitSet<N>;
+
+    fn not(self) -> BitSet<N> { self.complement() }
+}
+
+impl<const N: usize> FromIterator<BitSetIndex<N>> for BitSet<N>
 {
-    fn from_iter<T: IntoIterator<Item = BitSetIndex>>(iter: T) -> BitSet
+    fn from_iter<T: IntoIterator<Item = BitSetIndex<N>>>(iter: T) -> BitSet<N>
     {
         let mut set = BitSet::new_empty();
         for i in iter
@@ -83,49 +195,92 @@ mod tests
     #[test]
     fn bitset() -> Result<(), anyhow::Error>
     {
-        let mut a = BitSet::new_empty();
-        let mut b = BitSet::new_empty();
+        let mut a: BitSet = BitSet::new_empty();
+        let mut b: BitSet = BitSet::new_empty();
 
         println!("a: {:?}, b: {:?}", a, b);
-        assert_eq!(
-            BitSet::intersect(&a, &b).get_set_bits().len() == 0,
-            true
+        assert!(
+            BitSet::intersect(&a, &b).is_empty()
         );
 
         a.set(&BitSetIndex::new(63).unwrap());
         println!("a: {:?}, b: {:?}", a, b);
-        assert_eq!(
-            BitSet::intersect(&a, &b).get_set_bits().len() == 0,
-            true
+        assert!(
+            BitSet::intersect(&a, &b).is_empty()
         );
 
         b.set(&BitSetIndex::new(0).unwrap());
         println!("a: {:?}, b: {:?}", a, b);
-        assert_eq!(
-            BitSet::intersect(&a, &b).get_set_bits().len() == 0,
-            true
+        assert!(
+            BitSet::intersect(&a, &b).is_empty()
         );
 
         a.set(&BitSetIndex::new(0).unwrap());
         println!("a: {:?}, b: {:?}", a, b);
-        assert_eq!(
-            BitSet::intersect(&a, &b).get_set_bits().len() == 1,
-            true
+        assert!(
+            BitSet::intersect(&a, &b).len() == 1
         );
 
-        assert_eq!(
-            BitSet::extend(&a, &b).get_set_bits().len() == 2,
-            true
+        assert!(
+            BitSet::extend(&a, &b).len() == 2
         );
 
-        assert_eq!(
+        assert!(
             [1, 2, 3, 1].into_iter()
                 .map(|index| BitSetIndex::new(index).unwrap())
                 .collect::<BitSet>()
-                .get_set_bits().len() == 3,
-            true
+                .len() == 3
         );
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn wide_bitset() -> Result<(), anyhow::Error>
+    {
+        assert!(BitSetIndex::<1>::new(64).is_err());
+        assert!(BitSetIndex::<4>::new(256).is_err());
+
+        let a = [0, 63, 64, 200, 255].into_iter()
+            .map(BitSetIndex::<4>::new)
+            .collect::<Result<BitSet<4>, _>>()?;
+        let b = [64, 65, 255].into_iter()
+            .map(BitSetIndex::<4>::new)
+            .collect::<Result<BitSet<4>, _>>()?;
+
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![0, 63, 64, 200, 255]);
+        assert_eq!(BitSet::intersect(&a, &b).iter().collect::<Vec<_>>(), vec![64, 255]);
+        assert_eq!(BitSet::extend(&a, &b).len(), 6);
+        assert_eq!(BitSet::new(1 << 5).iter().collect::<Vec<_>>(), vec![5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_algebra() -> Result<(), anyhow::Error>
+    {
+        let index = BitSetIndex::<2>::new;
+        let a = [1, 5, 70, 127].into_iter().map(index).collect::<Result<BitSet<2>, _>>()?;
+        let b = [5, 70, 100].into_iter().map(index).collect::<Result<BitSet<2>, _>>()?;
+
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![5, 70]);
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 5, 70, 100, 127]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![1, 100, 127]);
+        assert_eq!(BitSet::difference(&a, &b).iter().collect::<Vec<_>>(), vec![1, 127]);
+        assert_eq!((!a).len(), 128 - 4);
+        assert!((!a & a).is_empty());
+
+        assert!((a & b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!(a.contains(&index(127)?));
+        assert!(!a.contains(&index(126)?));
+
+        let mut c = a;
+        c.remove(&index(70)?);
+        c.remove(&index(71)?);
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![1, 5, 127]);
+        assert_eq!(c.len(), 3);
+
+        Ok(())
+    }
+}
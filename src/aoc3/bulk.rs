@@ -0,0 +1,191 @@
+use super::alphabet::Alphabet;
+use super::rucksack::{ItemSet, ITEM_SET_WORDS};
+
+const INVALID: u8 = u8::MAX;
+
+// Parses rucksack lines byte by byte straight into one bit set per pocket, without building
+// `Item`s or `Pocket`s. Only works for ascii alphabets and two pockets, which is all the puzzle
+// input needs. Lines end with "\n", "\r\n" or the end of the input.
+pub struct BulkParser
+{
+    lookup: [u8; 256],
+    priorities: Vec<u32>,
+}
+
+impl BulkParser
+{
+    pub fn new(alphabet: &Alphabet) -> Result<BulkParser, anyhow::Error>
+    {
+        if alphabet.len() >= INVALID as usize
+        {
+            return Err(anyhow::Error::msg(format!("the bulk parser can't read alphabets of {} symbols.", alphabet.len())));
+        }
+
+        let mut lookup = [INVALID; 256];
+        for item in alphabet.items()
+        {
+            let symbol = item.get_symbol();
+            if !symbol.is_ascii()
+            {
+                return Err(anyhow::Error::msg(format!("symbol '{symbol}' isn't ascii, the bulk parser can't read it.")));
+            }
+            lookup[symbol as usize] = item.get_index() as u8;
+        }
+
+        let priorities = alphabet.items().iter().map(|item| item.get_prio()).collect();
+        Ok(BulkParser { lookup, priorities })
+    }
+
+    // The item types of both pockets of a line without its line ending.
+    pub fn parse_line(&self, line: &[u8]) -> Option<[ItemSet; 2]>
+    {
+        if line.len() % 2 != 0
+        {
+            return None;
+        }
+
+        let (left, right) = line.split_at(line.len() / 2);
+        Some([self.parse_pocket(left)?, self.parse_pocket(right)?])
+    }
+
+    fn parse_pocket(&self, pocket: &[u8]) -> Option<ItemSet>
+    {
+        let mut words = [0u64; ITEM_SET_WORDS];
+        for byte in pocket
+        {
+            let index = self.lookup[*byte as usize];
+            if index == INVALID
+            {
+                return None;
+            }
+            words[index as usize / 64] |= 1 << (index % 64);
+        }
+
+        Some(ItemSet::from_words(words))
+    }
+
+    // Calls `f` with the pockets of every line, stopping at the first invalid one.
+    pub fn for_each_rucksack(&self, input: &[u8], mut f: impl FnMut([ItemSet; 2])) -> Result<(), anyhow::Error>
+    {
+        let input = input.strip_suffix(b"\n").unwrap_or(input);
+        if input.is_empty()
+        {
+            return Ok(());
+        }
+
+        for (line_index, line) in input.split(|byte| *byte == b'\n').enumerate()
+        {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let pockets = self.parse_line(line)
+                .ok_or_else(|| anyhow::Error::msg(format!(
+                    "line {}: '{}' is not a valid rucksack.", line_index + 1, String::from_utf8_lossy(line))))?;
+            f(pockets);
+        }
+
+        Ok(())
+    }
+
+    // The priorities of the items found in both pockets, over all rucksacks.
+    pub fn sum_duplicate_priorities(&self, input: &[u8]) -> Result<u64, anyhow::Error>
+    {
+        let mut sum = 0u64;
+        self.for_each_rucksack(input, |[left, right]|
+        {
+            sum += (left & right).iter().map(|index| self.priorities[index] as u64).sum::<u64>();
+        })?;
+
+        Ok(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::bitset::BitSetIndex;
+    use super::super::rucksack::{parse_rucksack, Rucksack};
+
+    fn generate_rucksacks(num_rucksacks: usize, line_ending: &str) -> String
+    {
+        let symbols = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        let mut state = 1u64;
+        let mut next_below = |bound: usize|
+        {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % bound as u64) as usize
+        };
+
+        (0..num_rucksacks)
+            .map(|_| (0..2 * (1 + next_below(16))).map(|_| symbols[next_below(symbols.len())]).collect::<String>())
+            .collect::<Vec<_>>()
+            .join(line_ending)
+    }
+
+    fn reference_sum(input: &str, alphabet: &Alphabet) -> u64
+    {
+        input.lines()
+            .map(|line| parse_rucksack(line, 2, alphabet).and_then(|rucksack| Rucksack::find_duplicates_in_pockets(&rucksack, alphabet)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .iter().flatten()
+            .map(|item| item.get_prio() as u64)
+            .sum()
+    }
+
+    #[test]
+    fn matches_pocket_path()
+    {
+        let alphabet = Alphabet::standard();
+        let parser = BulkParser::new(&alphabet).unwrap();
+
+        for line_ending in ["\n", "\r\n"]
+        {
+            let input = generate_rucksacks(1000, line_ending);
+            assert_eq!(parser.sum_duplicate_priorities(input.as_bytes()).unwrap(), reference_sum(&input, &alphabet));
+
+            let input = input + line_ending;
+            assert_eq!(parser.sum_duplicate_priorities(input.as_bytes()).unwrap(), reference_sum(&input, &alphabet));
+        }
+
+        let [left, right] = parser.parse_line(b"abcA").unwrap();
+        assert!(left.contains(&BitSetIndex::new(1).unwrap()) && right.contains(&BitSetIndex::new(26).unwrap()));
+        assert!(parser.parse_line(b"abc").is_none());
+    }
+
+    #[test]
+    fn invalid_lines()
+    {
+        let parser = BulkParser::new(&Alphabet::standard()).unwrap();
+
+        assert_eq!(parser.sum_duplicate_priorities(b"abca\nabc\nabab").unwrap_err().to_string(), "line 2: 'abc' is not a valid rucksack.");
+        assert_eq!(parser.sum_duplicate_priorities(b"abca\r\nab1b\r\n").unwrap_err().to_string(), "line 2: 'ab1b' is not a valid rucksack.");
+        assert_eq!(parser.sum_duplicate_priorities(b"abca\n\nabab").unwrap(), 1 + 3);
+        assert_eq!(parser.sum_duplicate_priorities(b"").unwrap(), 0);
+
+        assert!(BulkParser::new(&Alphabet::new(&[('♠', 1)]).unwrap()).is_err());
+    }
+
+    // cargo test --release bulk_parse_throughput -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bulk_parse_throughput()
+    {
+        let alphabet = Alphabet::standard();
+        let parser = BulkParser::new(&alphabet).unwrap();
+        let input = generate_rucksacks(2_000_000, "\n");
+
+        let start = std::time::Instant::now();
+        let sum = parser.sum_duplicate_priorities(input.as_bytes()).unwrap();
+        let bulk_seconds = start.elapsed().as_secs_f64();
+
+        let start = std::time::Instant::now();
+        let reference = reference_sum(&input, &alphabet);
+        let pocket_seconds = start.elapsed().as_secs_f64();
+
+        assert_eq!(sum, reference);
+        println!(
+            "bulk: {bulk_seconds:.3}s, {:.0} MB/s; pockets: {pocket_seconds:.3}s, {:.0} MB/s",
+            input.len() as f64 / bulk_seconds / 1e6, input.len() as f64 / pocket_seconds / 1e6
+        );
+    }
+}
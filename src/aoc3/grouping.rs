@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+#[derive(Debug,Clone,PartialEq)]
+pub enum GroupingMode
+{
+    // Every `group_size` rucksacks in a row form a group.
+    Consecutive,
+    // Every run of `group_size` neighbouring rucksacks forms a group, so groups overlap.
+    Sliding,
+    // Rucksacks with the same id form a group, one id per rucksack.
+    GroupIds(Vec<String>),
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Group
+{
+    pub id: Option<String>,
+    // Indices of the rucksacks in input order.
+    pub members: Vec<usize>,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Grouping
+{
+    pub group_size: usize,
+    pub groups: Vec<Group>,
+    // Groups with fewer than `group_size` rucksacks, like a trailing one.
+    pub incomplete: Vec<Group>,
+}
+
+impl Group
+{
+    pub(super) fn new(id: Option<String>, members: Vec<usize>) -> Group
+    {
+        Group { id, members }
+    }
+
+    pub fn describe(&self) -> String
+    {
+        let lines = self.members.iter().map(|member| (member + 1).to_string()).collect::<Vec<_>>().join(", ");
+        match &self.id
+        {
+            Some(id) => format!("group '{id}' on lines {lines}"),
+            None => format!("group on lines {lines}"),
+        }
+    }
+}
+
+pub fn group_rucksacks(num_rucksacks: usize, group_size: usize, mode: &GroupingMode) -> Result<Grouping, anyhow::Error>
+{
+    if group_size == 0
+    {
+        return Err(anyhow::Error::msg("A group needs at least one rucksack."));
+    }
+
+    let indices = (0..num_rucksacks).collect::<Vec<_>>();
+
+    let all_groups = match mode
+    {
+        GroupingMode::Consecutive =>
+            indices.chunks(group_size)
+                .map(|members| Group::new(None, members.to_vec()))
+                .collect::<Vec<_>>(),
+        GroupingMode::Sliding if num_rucksacks < group_size =>
+            vec![Group::new(None, indices)],
+        GroupingMode::Sliding =>
+            indices.windows(group_size)
+                .map(|members| Group::new(None, members.to_vec()))
+                .collect::<Vec<_>>(),
+        GroupingMode::GroupIds(ids) =>
+        {
+            if ids.len() != num_rucksacks
+            {
+                return Err(anyhow::Error::msg(format!("There are {} group ids for {num_rucksacks} rucksacks.", ids.len())));
+            }
+
+            let mut order = Vec::new();
+            let mut members = HashMap::<&str, Vec<usize>>::new();
+            for (index, id) in ids.iter().enumerate()
+            {
+                members.entry(id).or_insert_with(|| { order.push(id); Vec::new() }).push(index);
+            }
+
+            order.into_iter()
+                .map(|id| Group::new(Some(id.clone()), members.remove(id.as_str()).unwrap_or_default()))
+                .collect::<Vec<_>>()
+        },
+    };
+
+    if let Some(group) = all_groups.iter().find(|group| group.members.len() > group_size)
+    {
+        return Err(anyhow::Error::msg(format!("The {} has more than {group_size} rucksacks.", group.describe())));
+    }
+
+    let (groups, incomplete) = all_groups.into_iter()
+        .filter(|group| !group.members.is_empty())
+        .partition(|group| group.members.len() == group_size);
+
+    Ok(Grouping { group_size, groups, incomplete })
+}
+
+// Splits "<rucksack> <group id>" lines.
+pub fn parse_group_id_line(line: &str) -> Result<(&str, &str), anyhow::Error>
+{
+    line.trim().split_once(char::is_whitespace)
+        .map(|(rucksack, id)| (rucksack, id.trim()))
+        .ok_or_else(|| anyhow::Error::msg(format!("Line '{line}' doesn't have a group id column.")))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn members(groups: &[Group]) -> Vec<Vec<usize>>
+    {
+        groups.iter().map(|group| group.members.clone()).collect()
+    }
+
+    #[test]
+    fn modes() -> Result<(), anyhow::Error>
+    {
+        let grouping = group_rucksacks(7, 3, &GroupingMode::Consecutive)?;
+        assert_eq!(members(&grouping.groups), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(members(&grouping.incomplete), vec![vec![6]]);
+        assert_eq!(grouping.incomplete[0].describe(), "group on lines 7");
+
+        let grouping = group_rucksacks(4, 3, &GroupingMode::Sliding)?;
+        assert_eq!(members(&grouping.groups), vec![vec![0, 1, 2], vec![1, 2, 3]]);
+        assert!(grouping.incomplete.is_empty());
+
+        let ids = ["b", "a", "b", "a", "c"].map(String::from).to_vec();
+        let grouping = group_rucksacks(5, 2, &GroupingMode::GroupIds(ids.clone()))?;
+        assert_eq!(members(&grouping.groups), vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(grouping.incomplete[0].describe(), "group 'c' on lines 5");
+
+        assert!(group_rucksacks(5, 1, &GroupingMode::GroupIds(ids.clone())).is_err());
+        assert!(group_rucksacks(4, 2, &GroupingMode::GroupIds(ids)).is_err());
+        assert!(group_rucksacks(4, 0, &GroupingMode::Consecutive).is_err());
+
+        assert_eq!(parse_group_id_line("abAB  elves-1")?, ("abAB", "elves-1"));
+        assert!(parse_group_id_line("abAB").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use super::alphabet::Alphabet;
+use super::bitset::BitSetIndex;
+use super::rucksack::{Item, ItemSet, Rucksack};
+
+// Answers queries about which rucksacks hold which items, built once from all rucksacks. Every
+// rucksack has a row of the item types it holds and every item type a posting list of the
+// rucksacks holding it, in input order.
+#[derive(Debug,Clone)]
+pub struct InventoryIndex
+{
+    items: Vec<Item>,
+    rows: Vec<ItemSet>,
+    postings: Vec<Vec<usize>>,
+    occurrences: Vec<usize>,
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Frequency
+{
+    pub item: Item,
+    // Rucksacks holding the item at least once.
+    pub rucksacks: usize,
+    // Copies of the item over all rucksacks.
+    pub occurrences: usize,
+}
+
+impl InventoryIndex
+{
+    pub fn new(rucksacks: &[Rucksack], alphabet: &Alphabet) -> Result<InventoryIndex, anyhow::Error>
+    {
+        let mut postings = vec![Vec::new(); alphabet.len()];
+        let mut occurrences = vec![0; alphabet.len()];
+
+        let rows = rucksacks.iter()
+            .map(|rucksack| rucksack.to_bitset())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (rucksack_index, (rucksack, row)) in rucksacks.iter().zip(rows.iter()).enumerate()
+        {
+            row.iter().for_each(|item_index| postings[item_index].push(rucksack_index));
+            rucksack.get_pockets().iter()
+                .flat_map(|pocket| pocket.get_items())
+                .for_each(|item| occurrences[item.get_index()] += 1);
+        }
+
+        Ok(InventoryIndex { items: alphabet.items().to_vec(), rows, postings, occurrences })
+    }
+
+    pub fn num_rucksacks(&self) -> usize { self.rows.len() }
+
+    // Indices of the rucksacks holding the item.
+    pub fn find_rucksacks_with(&self, item: Item) -> &[usize]
+    {
+        self.postings.get(item.get_index()).map(|posting| posting.as_slice()).unwrap_or_default()
+    }
+
+    pub fn get_frequency(&self, item: Item) -> Frequency
+    {
+        Frequency
+        {
+            item,
+            rucksacks: self.find_rucksacks_with(item).len(),
+            occurrences: self.occurrences.get(item.get_index()).copied().unwrap_or_default(),
+        }
+    }
+
+    // Every item of the alphabet found in any rucksack, in alphabet order.
+    pub fn frequencies(&self) -> Vec<Frequency>
+    {
+        self.items.iter()
+            .map(|item| self.get_frequency(*item))
+            .filter(|frequency| frequency.rucksacks > 0)
+            .collect()
+    }
+
+    // Items held by every one of the given rucksacks.
+    pub fn find_common_items(&self, rucksacks: &[usize]) -> Result<Vec<Item>, anyhow::Error>
+    {
+        let mut common = ItemSet::new_empty().complement();
+        for rucksack in rucksacks
+        {
+            let row = self.rows.get(*rucksack)
+                .ok_or_else(|| anyhow::Error::msg(format!("There is no rucksack {rucksack}, only {}.", self.num_rucksacks())))?;
+            common = common & *row;
+        }
+
+        Ok(common.iter()
+            .take_while(|index| *index < self.items.len())
+            .map(|index| self.items[index])
+            .collect())
+    }
+
+    pub fn contains(&self, rucksack: usize, item: Item) -> bool
+    {
+        match (self.rows.get(rucksack), BitSetIndex::new(item.get_index()))
+        {
+            (Some(row), Ok(index)) => row.contains(&index),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::rucksack::parse_rucksack;
+
+    #[test]
+    fn queries() -> Result<(), anyhow::Error>
+    {
+        let alphabet = Alphabet::standard();
+        let rucksacks = ["abca", "bbcd", "xaby"]
+            .iter()
+            .map(|line| parse_rucksack(line, 2, &alphabet))
+            .collect::<Result<Vec<_>, _>>()?;
+        let index = InventoryIndex::new(&rucksacks, &alphabet)?;
+        let item = |symbol| alphabet.get_item(symbol).unwrap();
+
+        assert_eq!(index.find_rucksacks_with(item('a')), &[0, 2]);
+        assert_eq!(index.find_rucksacks_with(item('z')), &[] as &[usize]);
+        assert!(index.contains(1, item('d')) && !index.contains(1, item('a')) && !index.contains(7, item('a')));
+
+        assert_eq!(index.get_frequency(item('b')), Frequency { item: item('b'), rucksacks: 3, occurrences: 4 });
+        assert_eq!(index.frequencies().iter().map(|frequency| frequency.item.get_symbol()).collect::<String>(), "abcdxy");
+
+        assert_eq!(index.find_common_items(&[0, 1, 2])?, vec![item('b')]);
+        assert_eq!(index.find_common_items(&[0, 1])?, vec![item('b'), item('c')]);
+        assert_eq!(index.find_common_items(&[])?.len(), alphabet.len());
+        assert!(index.find_common_items(&[3]).is_err());
+
+        Ok(())
+    }
+}
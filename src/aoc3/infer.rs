@@ -0,0 +1,186 @@
+use super::grouping::Group;
+use super::rucksack::{ItemSet, Rucksack};
+
+// Bounds the search, which is exponential in the worst case.
+const SEARCH_NODE_LIMIT: usize = 1_000_000;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct InferredGrouping
+{
+    // Groups sharing exactly one item, with members in input order.
+    pub groups: Vec<Group>,
+    pub ungrouped: Vec<usize>,
+    // No other grouping with as many groups exists.
+    pub is_unique: bool,
+    // The search finished within its node limit, so `groups` is the largest grouping there is.
+    pub is_exhaustive: bool,
+}
+
+struct Search<'a>
+{
+    sets: &'a [ItemSet],
+    group_size: usize,
+    assigned: Vec<bool>,
+    groups: Vec<Vec<usize>>,
+    best: Vec<Vec<usize>>,
+    num_best: usize,
+    nodes: usize,
+}
+
+impl InferredGrouping
+{
+    pub fn is_complete(&self) -> bool { self.ungrouped.is_empty() }
+}
+
+// Partitions rucksacks given in any order into groups that share exactly one item, the badge.
+// If there is no full partition, the largest set of such groups is returned instead.
+pub fn infer_groups(rucksacks: &[Rucksack], group_size: usize) -> Result<InferredGrouping, anyhow::Error>
+{
+    if group_size == 0
+    {
+        return Err(anyhow::Error::msg("A group needs at least one rucksack."));
+    }
+
+    let sets = rucksacks.iter()
+        .map(|rucksack| rucksack.to_bitset())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut search = Search
+    {
+        sets: &sets,
+        group_size,
+        assigned: vec![false; sets.len()],
+        groups: Vec::new(),
+        best: Vec::new(),
+        num_best: 0,
+        nodes: 0,
+    };
+    search.search(0);
+
+    let mut ungrouped = vec![true; sets.len()];
+    search.best.iter().flatten().for_each(|member| ungrouped[*member] = false);
+
+    Ok(InferredGrouping
+    {
+        groups: search.best.iter().map(|members| Group::new(None, members.clone())).collect(),
+        ungrouped: (0..sets.len()).filter(|index| ungrouped[*index]).collect(),
+        is_unique: search.num_best == 1 && search.nodes < SEARCH_NODE_LIMIT,
+        is_exhaustive: search.nodes < SEARCH_NODE_LIMIT,
+    })
+}
+
+impl Search<'_>
+{
+    // Either puts the first unassigned rucksack into a group with later ones or leaves it out.
+    fn search(&mut self, from: usize)
+    {
+        if self.nodes >= SEARCH_NODE_LIMIT
+        {
+            return;
+        }
+        self.nodes += 1;
+
+        let first = match (from..self.sets.len()).find(|index| !self.assigned[*index])
+        {
+            Some(first) => first,
+            None =>
+            {
+                match self.groups.len().cmp(&self.best.len())
+                {
+                    std::cmp::Ordering::Greater => { self.best = self.groups.clone(); self.num_best = 1; },
+                    std::cmp::Ordering::Equal => self.num_best += 1,
+                    std::cmp::Ordering::Less => (),
+                }
+                return;
+            },
+        };
+
+        // Once two best groupings are known, only a larger one matters.
+        let remaining = (first..self.sets.len()).filter(|index| !self.assigned[*index]).count();
+        let bound = self.groups.len() + remaining / self.group_size;
+        if bound < self.best.len() || (bound == self.best.len() && self.num_best >= 2)
+        {
+            return;
+        }
+
+        self.assigned[first] = true;
+        self.complete_group(&mut vec![first], self.sets[first], first + 1);
+        self.search(first + 1);
+        self.assigned[first] = false;
+    }
+
+    fn complete_group(&mut self, members: &mut Vec<usize>, common: ItemSet, from: usize)
+    {
+        if members.len() == self.group_size
+        {
+            if common.len() == 1
+            {
+                self.groups.push(members.clone());
+                self.search(members[0] + 1);
+                self.groups.pop();
+            }
+            return;
+        }
+
+        for index in from..self.sets.len()
+        {
+            let common = common & self.sets[index];
+            if self.assigned[index] || common.is_empty()
+            {
+                continue;
+            }
+
+            self.assigned[index] = true;
+            members.push(index);
+            self.complete_group(members, common, index + 1);
+            members.pop();
+            self.assigned[index] = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::alphabet::Alphabet;
+    use super::super::rucksack::parse_rucksack;
+
+    fn infer(lines: &[&str]) -> Result<InferredGrouping, anyhow::Error>
+    {
+        let alphabet = Alphabet::standard();
+        let rucksacks = lines.iter()
+            .map(|line| parse_rucksack(line, 2, &alphabet))
+            .collect::<Result<Vec<_>, _>>()?;
+        infer_groups(&rucksacks, 3)
+    }
+
+    fn members(grouping: &InferredGrouping) -> Vec<Vec<usize>>
+    {
+        grouping.groups.iter().map(|group| group.members.clone()).collect()
+    }
+
+    #[test]
+    fn inference() -> Result<(), anyhow::Error>
+    {
+        let grouping = infer(&["ab", "xy", "ac", "xz", "ad", "xw"])?;
+        assert_eq!(members(&grouping), vec![vec![0, 2, 4], vec![1, 3, 5]]);
+        assert!(grouping.is_complete() && grouping.is_unique && grouping.is_exhaustive);
+
+        // Any three of the first four share only 'a'.
+        let grouping = infer(&["ab", "ac", "ad", "ae", "xy", "xz"])?;
+        assert_eq!(grouping.groups.len(), 1);
+        assert_eq!(grouping.ungrouped.len(), 3);
+        assert!(!grouping.is_complete() && !grouping.is_unique);
+
+        // Every rucksack shares 'a' and 'b' with the others, so no group has a single badge.
+        let grouping = infer(&["ab", "ab", "ab"])?;
+        assert!(grouping.groups.is_empty());
+        assert_eq!(grouping.ungrouped, vec![0, 1, 2]);
+        assert!(grouping.is_unique);
+
+        assert!(infer_groups(&[], 0).is_err());
+
+        Ok(())
+    }
+}
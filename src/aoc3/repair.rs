@@ -0,0 +1,219 @@
+use super::bitset::BitSetIndex;
+use super::rucksack::{Item, ItemSet, Rucksack};
+
+// Swaps an item of the left pocket with one of the right pocket, by position. Swapping is the only
+// way to move items while keeping the pocket sizes.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Swap
+{
+    pub left: usize,
+    pub right: usize,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct RepairPlan
+{
+    pub swaps: Vec<Swap>,
+    // The item types that end up in each pocket.
+    pub left_items: Vec<Item>,
+    pub right_items: Vec<Item>,
+}
+
+#[derive(Debug,Clone,Copy)]
+struct ItemCount
+{
+    item: Item,
+    left: usize,
+    right: usize,
+}
+
+// Finds the fewest swaps that leave no item type in both pockets of a two pocket rucksack, or
+// `None` if no split of the item types fits the pocket sizes.
+//
+// Every item type ends up entirely in one pocket, so the problem is picking the types for the left
+// pocket so that their counts add up to its size, while moving as few items from the right pocket
+// as possible. That is a knapsack over the item types, solved exactly by dynamic programming.
+pub fn plan_repair(rucksack: &Rucksack) -> Result<Option<RepairPlan>, anyhow::Error>
+{
+    let pockets = match rucksack.get_pockets()
+    {
+        [left, right] => [left.get_items(), right.get_items()],
+        _ => return Err(anyhow::Error::msg(format!("Can only repair rucksacks with 2 pockets, not {}.", rucksack.num_pockets()))),
+    };
+
+    let pocket_sets = rucksack.pocket_bitsets()?;
+    if (pocket_sets[0] & pocket_sets[1]).is_empty()
+    {
+        return Ok(Some(RepairPlan
+        {
+            swaps: Vec::new(),
+            left_items: distinct_items(pockets[0]),
+            right_items: distinct_items(pockets[1]),
+        }));
+    }
+
+    let counts = count_items(pockets[0], pockets[1]);
+    let left_size = pockets[0].len();
+
+    // costs[i][size] is the fewest items moved into the left pocket when the first i item types
+    // fill `size` places of it, with the choice for type i - 1 kept to walk back the solution.
+    let mut costs = vec![vec![None; left_size + 1]; counts.len() + 1];
+    let mut in_left = vec![vec![false; left_size + 1]; counts.len() + 1];
+    costs[0][0] = Some(0);
+
+    for (index, count) in counts.iter().enumerate()
+    {
+        let total = count.left + count.right;
+        for size in 0..=left_size
+        {
+            let stay_right = costs[index][size];
+            let go_left = match size >= total
+            {
+                true => costs[index][size - total].map(|cost| cost + count.right),
+                false => None,
+            };
+
+            (costs[index + 1][size], in_left[index + 1][size]) = match (stay_right, go_left)
+            {
+                (Some(right), Some(left)) if left < right => (Some(left), true),
+                (None, Some(left)) => (Some(left), true),
+                (right, _) => (right, false),
+            };
+        }
+    }
+
+    if costs[counts.len()][left_size].is_none()
+    {
+        return Ok(None);
+    }
+
+    let mut left_types = ItemSet::new_empty();
+    let mut size = left_size;
+    for index in (0..counts.len()).rev()
+    {
+        if in_left[index + 1][size]
+        {
+            left_types.set(&BitSetIndex::new(counts[index].item.get_index())?);
+            size -= counts[index].left + counts[index].right;
+        }
+    }
+
+    let belongs_left = |item: &Item| BitSetIndex::new(item.get_index()).is_ok_and(|index| left_types.contains(&index));
+    let misplaced_left = pockets[0].iter().enumerate().filter(|(_, item)| !belongs_left(item)).map(|(position, _)| position);
+    let misplaced_right = pockets[1].iter().enumerate().filter(|(_, item)| belongs_left(item)).map(|(position, _)| position);
+
+    let (left_items, right_items) = counts.iter()
+        .map(|count| count.item)
+        .partition(|item| belongs_left(item));
+
+    Ok(Some(RepairPlan
+    {
+        swaps: misplaced_left.zip(misplaced_right).map(|(left, right)| Swap { left, right }).collect(),
+        left_items,
+        right_items,
+    }))
+}
+
+fn distinct_items(items: &[Item]) -> Vec<Item>
+{
+    let mut distinct = items.to_vec();
+    distinct.sort_by_key(|item| item.get_index());
+    distinct.dedup();
+    distinct
+}
+
+fn count_items(left: &[Item], right: &[Item]) -> Vec<ItemCount>
+{
+    let mut counts = distinct_items(&[left, right].concat()).into_iter()
+        .map(|item| ItemCount { item, left: 0, right: 0 })
+        .collect::<Vec<_>>();
+
+    let mut count = |item: &Item, is_left: bool|
+    {
+        let count = counts.iter_mut().find(|count| count.item == *item).expect("every item was counted");
+        match is_left
+        {
+            true => count.left += 1,
+            false => count.right += 1,
+        }
+    };
+
+    left.iter().for_each(|item| count(item, true));
+    right.iter().for_each(|item| count(item, false));
+    counts
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::{HashSet, VecDeque};
+
+    use super::*;
+    use super::super::alphabet::Alphabet;
+    use super::super::rucksack::parse_rucksack;
+
+    // Breadth first search over all swap sequences, as a reference for short rucksacks.
+    fn fewest_swaps(rucksack_str: &str) -> Option<usize>
+    {
+        let half = rucksack_str.len() / 2;
+        let is_disjoint = |state: &[u8]| !state[..half].iter().any(|byte| state[half..].contains(byte));
+
+        let mut seen = HashSet::from([rucksack_str.as_bytes().to_vec()]);
+        let mut queue = VecDeque::from([(rucksack_str.as_bytes().to_vec(), 0)]);
+        while let Some((state, swaps)) = queue.pop_front()
+        {
+            if is_disjoint(&state)
+            {
+                return Some(swaps);
+            }
+
+            for left in 0..half
+            {
+                for right in half..state.len()
+                {
+                    let mut next = state.clone();
+                    next.swap(left, right);
+                    if seen.insert(next.clone())
+                    {
+                        queue.push_back((next, swaps + 1));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn apply(rucksack_str: &str, plan: &RepairPlan) -> (String, String)
+    {
+        let mut chars = rucksack_str.chars().collect::<Vec<_>>();
+        let half = chars.len() / 2;
+        plan.swaps.iter().for_each(|swap| chars.swap(swap.left, half + swap.right));
+        (chars[..half].iter().collect(), chars[half..].iter().collect())
+    }
+
+    #[test]
+    fn repairs() -> Result<(), anyhow::Error>
+    {
+        let alphabet = Alphabet::standard();
+
+        for rucksack_str in ["abca", "abcd", "aaab", "abcabc", "aabbcc", "vJrwpWtwJgWrhcsFMMfFFhFp", "aabcabcc", "abcdeafa"]
+        {
+            let rucksack = parse_rucksack(rucksack_str, 2, &alphabet)?;
+            let plan = plan_repair(&rucksack)?;
+            assert_eq!(plan.as_ref().map(|plan| plan.swaps.len()), fewest_swaps(rucksack_str), "{rucksack_str}");
+
+            if let Some(plan) = plan
+            {
+                let (left, right) = apply(rucksack_str, &plan);
+                assert!(!left.chars().any(|char| right.contains(char)), "{rucksack_str} became {left} {right}");
+                assert!(left.chars().all(|char| plan.left_items.iter().any(|item| item.get_symbol() == char)));
+                assert!(right.chars().all(|char| plan.right_items.iter().any(|item| item.get_symbol() == char)));
+            }
+        }
+
+        assert!(plan_repair(&parse_rucksack("abc", 3, &alphabet)?).is_err());
+
+        Ok(())
+    }
+}
@@ -1,11 +1,18 @@
 use std::{hash::Hash};
 
+use super::alphabet::Alphabet;
 use super::bitset::{BitSet, BitSetIndex};
 
+// Room for alphabets of up to 256 symbols.
+pub const ITEM_SET_WORDS: usize = 4;
+pub type ItemSet = BitSet<ITEM_SET_WORDS>;
+
 #[derive(Debug,Clone,Copy,Hash,Eq,PartialEq)]
 pub struct Item
 {
-    prio: u8,
+    symbol: char,
+    index: u16,
+    prio: u32,
 }
 
 #[derive(Debug)]
@@ -17,65 +24,110 @@ pub struct Pocket
 #[derive(Debug)]
 pub struct Rucksack
 {
-    left_pocket: Pocket,
-    right_pocket: Pocket,
+    pockets: Vec<Pocket>,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct DuplicateLocation
+{
+    pub item: Item,
+    pub pockets: Vec<usize>,
 }
 
 impl Rucksack {
-    fn new(left_pocket: Pocket, right_pocket: Pocket) -> Rucksack
+    fn new(pockets: Vec<Pocket>) -> Rucksack
     {
-        Rucksack
-        {
-            left_pocket: left_pocket,
-            right_pocket: right_pocket,
-        }
+        Rucksack { pockets }
     }
 
-    fn new_empty() -> Rucksack
+    fn new_empty(num_pockets: usize) -> Rucksack
     {
-        Rucksack::new(Pocket::new_empty(), Pocket::new_empty())
+        Rucksack::new((0..num_pockets).map(|_| Pocket::new_empty()).collect())
     }
 
-    fn to_bitset(&self) -> Result<BitSet, anyhow::Error>
+    pub fn num_pockets(&self) -> usize { self.pockets.len() }
+
+    pub fn get_pockets(&self) -> &[Pocket] { &self.pockets }
+
+    pub fn to_bitset(&self) -> Result<ItemSet, anyhow::Error>
     {
-        let left_set = self.left_pocket.to_bitset()?;
-        let right_set = self.right_pocket.to_bitset()?;
-        Ok(BitSet::extend(&left_set, &right_set))
+        self.pocket_bitsets()
+            .map(|sets| sets.into_iter().fold(BitSet::new_empty(), |a, b| a | b))
     }
 
-    pub fn find_duplicates_in_pockets(rucksack: &Rucksack) -> Result<Vec<Item>, anyhow::Error>
+    pub fn pocket_bitsets(&self) -> Result<Vec<ItemSet>, anyhow::Error>
     {
-        let left_set = rucksack.left_pocket.to_bitset()?;
-        let right_set = rucksack.right_pocket.to_bitset()?;
-        let duplicate_set = BitSet::intersect(&left_set, &right_set);
-        
-        let duplicate_items = duplicate_set.get_set_bits().into_iter()
-            .map(|prio| Item::new(prio))
-            .collect::<Result<Vec<_>, _>>()?;
+        self.pockets.iter()
+            .map(|pocket| pocket.to_bitset())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    // Items found in at least two of the pockets.
+    pub fn find_duplicates_in_pockets(rucksack: &Rucksack, alphabet: &Alphabet) -> Result<Vec<Item>, anyhow::Error>
+    {
+        let (_, duplicate_set) = rucksack.pocket_bitsets()?.into_iter()
+            .fold(
+                (BitSet::new_empty(), BitSet::new_empty()),
+                |(seen, duplicates), set| (seen | set, duplicates | (seen & set))
+            );
         
-        Ok(duplicate_items)
+        to_items(&duplicate_set, alphabet)
+    }
+
+    pub fn find_items_in_all_pockets(rucksack: &Rucksack, alphabet: &Alphabet) -> Result<Vec<Item>, anyhow::Error>
+    {
+        let common_set = rucksack.pocket_bitsets()?.into_iter()
+            .reduce(|a, b| a & b)
+            .unwrap_or_else(BitSet::new_empty);
+
+        to_items(&common_set, alphabet)
+    }
+
+    // Every item found in at least two pockets, with the indices of the pockets holding it.
+    pub fn find_duplicate_locations(rucksack: &Rucksack, alphabet: &Alphabet) -> Result<Vec<DuplicateLocation>, anyhow::Error>
+    {
+        let pocket_sets = rucksack.pocket_bitsets()?;
+
+        Rucksack::find_duplicates_in_pockets(rucksack, alphabet)?.into_iter()
+            .map(|item|
+            {
+                let index = item.to_bitset_index()?;
+                let pockets = pocket_sets.iter().enumerate()
+                    .filter(|(_, set)| set.contains(&index))
+                    .map(|(pocket, _)| pocket)
+                    .collect();
+                Ok(DuplicateLocation { item, pockets })
+            })
+            .collect()
     }
 
-    pub fn find_duplicates_in_rucksacks(rucksacks: &[Rucksack]) -> Result<Vec<Item>, anyhow::Error>
+    pub fn find_duplicates_in_rucksacks<'a>(rucksacks: impl IntoIterator<Item = &'a Rucksack>, alphabet: &Alphabet) -> Result<Vec<Item>, anyhow::Error>
     {
-        let duplicate_prios = rucksacks.iter()
+        let duplicate_prios = rucksacks.into_iter()
             .map(|rucksack| rucksack.to_bitset())
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
-            .reduce(|a,b| BitSet::intersect(&a, &b)).ok_or(anyhow::Error::msg(""))
-            .map(|set| set.get_set_bits())?;
+            .reduce(|a, b| a & b).ok_or(anyhow::Error::msg(""))?;
         
-        duplicate_prios.into_iter()
-            .map(|prio| Item::new(prio))
-            .collect::<Result<Vec<_>, _>>()
+        to_items(&duplicate_prios, alphabet)
     }
 }
 
+fn to_items(set: &ItemSet, alphabet: &Alphabet) -> Result<Vec<Item>, anyhow::Error>
+{
+    set.iter()
+        .map(|index|
+            alphabet.get_item_by_index(index)
+                .ok_or_else(|| anyhow::Error::msg(format!("index '{index}' is not part of the alphabet!")))
+        )
+        .collect::<Result<Vec<_>, _>>()
+}
+
 impl Pocket
 {
     fn new(items: Vec<Item>) -> Pocket
     {
-        Pocket { items: items }
+        Pocket { items }
     }
 
     fn new_empty() -> Pocket
@@ -83,81 +135,77 @@ impl Pocket
         Pocket::new(Vec::new())
     }
 
-    fn to_bitset(&self) -> Result<BitSet, anyhow::Error>
+    pub fn get_items(&self) -> &[Item] { &self.items }
+
+    fn to_bitset(&self) -> Result<ItemSet, anyhow::Error>
     {
         self.items.iter()
-            .map(|item| BitSetIndex::new(item.get_prio()))
-            .collect::<Result<BitSet, _>>()
+            .map(|item| item.to_bitset_index())
+            .collect::<Result<ItemSet, _>>()
     }
 }
 
 impl Item
 {
-    fn new(prio: u8) -> Result<Item, anyhow::Error>
+    // Items come from an `Alphabet`, which hands out the indices.
+    pub(super) fn new(symbol: char, index: u16, prio: u32) -> Item
     {
-        match Item::is_valid(prio)
-        {
-            true => Ok(Item{prio: prio}),
-            false => Err(anyhow::Error::msg(format!("prio '{prio}' is not valid for item!")))
-        }
+        Item{symbol, index, prio}
     }
 
-    pub fn get_prio(&self) -> u8 { self.prio }
+    pub fn get_prio(&self) -> u32 { self.prio }
 
-    pub fn to_priority(char: char) -> Option<u8>
-    {
-        match char
-        {
-            'a'..='z' => Some(1 + (char as u8 - 'a' as u8)),
-            'A'..='Z' => Some(27 + (char as u8 - 'A' as u8)),
-            _ => None,
-        }
-    }
+    pub fn get_symbol(&self) -> char { self.symbol }
 
-    fn is_valid(prio: u8) -> bool
+    pub fn get_index(&self) -> usize { self.index as usize }
+
+    fn to_bitset_index(self) -> Result<BitSetIndex<ITEM_SET_WORDS>, anyhow::Error>
     {
-        match prio
-        {
-            1..=52 => true,
-            _ => false,
-        }
+        BitSetIndex::new(self.get_index())
     }
 }
 
-pub fn parse_rucksack(rucksack_str: &str) -> Result<Rucksack, anyhow::Error>
+pub fn parse_rucksack(rucksack_str: &str, num_pockets: usize, alphabet: &Alphabet) -> Result<Rucksack, anyhow::Error>
 {
-    if rucksack_str.len() % 2 == 1
+    if num_pockets == 0
     {
-        return Err(anyhow::Error::msg("The rucksack string '{rucksack_str}' needs to be divisible in half."));
+        return Err(anyhow::Error::msg("A rucksack needs at least one pocket."));
     }
 
-    if rucksack_str.is_empty()
+    let items = rucksack_str.chars()
+        .map(|char| parse_item(char, alphabet))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if items.len() % num_pockets != 0
     {
-        return Ok(Rucksack::new_empty());
+        return Err(anyhow::Error::msg(format!("The rucksack string '{rucksack_str}' needs to be divisible into {num_pockets} pockets.")));
     }
 
-    let rucksack_split = rucksack_str.split_at(rucksack_str.len() / 2);
+    if items.is_empty()
+    {
+        return Ok(Rucksack::new_empty(num_pockets));
+    }
+
+    let pockets = items.chunks_exact(items.len() / num_pockets)
+        .map(|items| Pocket::new(items.to_vec()))
+        .collect();
 
-    Ok(Rucksack::new(parse_pocket(rucksack_split.0)?, parse_pocket(rucksack_split.1)?))
+    Ok(Rucksack::new(pockets))
 }
 
-fn parse_pocket(pocket_str: &str) -> Result<Pocket, anyhow::Error>
+pub fn parse_pocket(pocket_str: &str, alphabet: &Alphabet) -> Result<Pocket, anyhow::Error>
 {
     let item_vec = pocket_str.chars()
-        .into_iter()
-        .map(|char| parse_item(char))
+        .map(|char| parse_item(char, alphabet))
         .collect::<Result<Vec<_>, _>>()?;
 
     Ok(Pocket::new(item_vec))
 }
 
-fn parse_item(char: char) -> Result<Item, anyhow::Error>
+fn parse_item(char: char, alphabet: &Alphabet) -> Result<Item, anyhow::Error>
 {
-    match Item::to_priority(char)
-    {
-        Some(prio) => Item::new(prio),
-        None => Err(anyhow::Error::msg(format!("char {char} is not a valid item"))),
-    }
+    alphabet.get_item(char)
+        .ok_or_else(|| anyhow::Error::msg(format!("char {char} is not a valid item")))
 }
 
 #[cfg(test)]
@@ -169,53 +217,78 @@ mod tests
     #[test]
     fn duplicate_items()
     {
-        let rucksack = Rucksack::new(
+        let alphabet = Alphabet::standard();
+        let parse_pocket = |pocket_str| parse_pocket(pocket_str, &alphabet);
+        let rucksack = Rucksack::new(vec![
             parse_pocket("aAbB").unwrap(),
             parse_pocket("cCdD").unwrap(),
-        );
+        ]);
 
-        assert_eq!(
+        assert!(
             diff(
-                &Rucksack::find_duplicates_in_pockets(&rucksack).unwrap(),
+                &Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet).unwrap(),
                 &Vec::<Item>::new()
-            ).is_empty(), true);
+            ).is_empty());
 
-        let rucksack = Rucksack::new(
+        let rucksack = Rucksack::new(vec![
             parse_pocket("aAbBCC").unwrap(),
             parse_pocket("cCdD").unwrap(),
-        );
+        ]);
 
-        let duplicates = Rucksack::find_duplicates_in_pockets(&rucksack);
+        let duplicates = Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet);
         println!("{:?}", duplicates);
-        assert_eq!(
-            diff(&duplicates.unwrap(), &Vec::<Item>::new()).len() == 1,
-            true
+        assert!(
+            diff(&duplicates.unwrap(), &Vec::<Item>::new()).len() == 1
         );
         
-        let rucksack = Rucksack::new(
+        let rucksack = Rucksack::new(vec![
             parse_pocket("aAbBCC").unwrap(),
             parse_pocket("cCdDb").unwrap(),
-        );
+        ]);
 
-        assert_eq!(
+        assert!(
             diff(
-                &Rucksack::find_duplicates_in_pockets(&rucksack).unwrap(),
+                &Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet).unwrap(),
                 &Vec::<Item>::new()
-            ).len() == 2,
-            true
+            ).len() == 2
         );
     }
 
+    #[test]
+    fn multiple_pockets() -> Result<(), anyhow::Error>
+    {
+        let alphabet = Alphabet::standard();
+        let rucksack = parse_rucksack("abcXbdXbefgX", 3, &alphabet)?;
+        assert_eq!(rucksack.num_pockets(), 3);
+
+        let item = |char| parse_item(char, &alphabet).unwrap();
+        assert_eq!(Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet)?, vec![item('b'), item('X')]);
+        assert_eq!(Rucksack::find_items_in_all_pockets(&rucksack, &alphabet)?, vec![item('X')]);
+        assert_eq!(Rucksack::find_duplicate_locations(&rucksack, &alphabet)?, vec![
+            DuplicateLocation { item: item('b'), pockets: vec![0, 1] },
+            DuplicateLocation { item: item('X'), pockets: vec![0, 1, 2] },
+        ]);
+
+        assert_eq!(parse_rucksack("vJrwpWtwJgWrhcsFMMfFFhFp", 2, &alphabet)
+            .and_then(|rucksack| Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet))?, vec![item('p')]);
+        assert!(parse_rucksack("abcd", 3, &alphabet).is_err());
+        assert!(parse_rucksack("abcd", 0, &alphabet).is_err());
+        assert_eq!(parse_rucksack("", 4, &alphabet)?.num_pockets(), 4);
+
+        Ok(())
+    }
+
     #[test]
     fn priorities() -> Result<(), anyhow::Error>
     {
+        let alphabet = Alphabet::standard();
         let items = ('a'..='z').chain('A'..='Z')
-            .map(|char| Item::new(Item::to_priority(char).unwrap()))
+            .map(|char| parse_item(char, &alphabet))
             .collect::<Result<Vec<_>, _>>()?;
 
         for (index, item) in items.iter().enumerate()
         {
-            let priority = index as u8 + 1;
+            let priority = index as u32 + 1;
             println!("{:?} == {priority}", item);
             assert_eq!(item.get_prio(), priority);
         }
@@ -223,7 +296,24 @@ mod tests
         Ok(())
     }
 
-    fn diff<T: Copy + Hash + Eq>(vec1: &Vec<T>, vec2: &Vec<T>) -> Vec<T>
+    #[test]
+    fn custom_alphabet() -> Result<(), anyhow::Error>
+    {
+        let alphabet = Alphabet::new(&[('♠', 10), ('♥', 20), ('♦', 30), ('♣', 40)])?;
+        let rucksack = parse_rucksack("♠♥♦♥", 2, &alphabet)?;
+
+        let duplicates = Rucksack::find_duplicates_in_pockets(&rucksack, &alphabet)?;
+        assert_eq!(duplicates.iter().map(|item| (item.get_symbol(), item.get_prio())).collect::<Vec<_>>(), vec![('♥', 20)]);
+        assert!(parse_rucksack("♠a", 2, &alphabet).is_err());
+
+        let digits = Alphabet::digits();
+        let rucksack = parse_rucksack("1234", 2, &digits)?;
+        assert!(Rucksack::find_duplicates_in_pockets(&rucksack, &digits)?.is_empty());
+
+        Ok(())
+    }
+
+    fn diff<T: Copy + Hash + Eq>(vec1: &[T], vec2: &[T]) -> Vec<T>
     {
         let set1 = vec1.iter().copied().collect::<HashSet<_>>();
         let set2 = vec2.iter().copied().collect::<HashSet<_>>();
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use super::alphabet::Alphabet;
+use super::grouping::Grouping;
+use super::rucksack::{Item, Rucksack};
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Validation
+{
+    // Any rucksack or group that doesn't have exactly one shared item is an error.
+    Strict,
+    // Such rucksacks and groups only produce warnings, and whatever they share is still counted.
+    Lenient,
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum DiagnosticKind
+{
+    MisplacedItem,
+    Badge,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Diagnostic
+{
+    pub kind: DiagnosticKind,
+    // "rucksack on line 4" or a group description.
+    pub location: String,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Validated
+{
+    pub items: Vec<Item>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let what = match self.kind
+        {
+            DiagnosticKind::MisplacedItem => "misplaced items",
+            DiagnosticKind::Badge => "badges",
+        };
+
+        let items = self.items.iter().map(|item| item.get_symbol().to_string()).collect::<Vec<_>>().join(", ");
+        match self.items.is_empty()
+        {
+            true => write!(f, "The {} has no {what}, expected one.", self.location),
+            false => write!(f, "The {} has {} {what} ({items}), expected one.", self.location, self.items.len()),
+        }
+    }
+}
+
+// The item in both pockets of every rucksack.
+pub fn validate_misplaced_items(rucksacks: &[Rucksack], alphabet: &Alphabet, validation: Validation) -> Result<Validated, anyhow::Error>
+{
+    let found = rucksacks.iter().enumerate()
+        .map(|(index, rucksack)|
+            Ok((format!("rucksack on line {}", index + 1), Rucksack::find_duplicates_in_pockets(rucksack, alphabet)?))
+        )
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    validate(found, DiagnosticKind::MisplacedItem, validation)
+}
+
+// The badge of every complete group.
+pub fn validate_badges(rucksacks: &[Rucksack], grouping: &Grouping, alphabet: &Alphabet, validation: Validation) -> Result<Validated, anyhow::Error>
+{
+    let found = grouping.groups.iter()
+        .map(|group|
+            Ok((
+                group.describe(),
+                Rucksack::find_duplicates_in_rucksacks(group.members.iter().map(|member| &rucksacks[*member]), alphabet)?
+            ))
+        )
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    validate(found, DiagnosticKind::Badge, validation)
+}
+
+fn validate(found: Vec<(String, Vec<Item>)>, kind: DiagnosticKind, validation: Validation) -> Result<Validated, anyhow::Error>
+{
+    let warnings = found.iter()
+        .filter(|(_, items)| items.len() != 1)
+        .map(|(location, items)| Diagnostic { kind, location: location.clone(), items: items.clone() })
+        .collect::<Vec<_>>();
+
+    if validation == Validation::Strict && !warnings.is_empty()
+    {
+        let messages = warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>();
+        return Err(anyhow::Error::msg(messages.join("\n")));
+    }
+
+    let items = found.into_iter().flat_map(|(_, items)| items).collect();
+    Ok(Validated { items, warnings })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use super::super::grouping::{self, GroupingMode};
+    use super::super::rucksack::parse_rucksack;
+
+    #[test]
+    fn diagnostics() -> Result<(), anyhow::Error>
+    {
+        let alphabet = Alphabet::standard();
+        let rucksacks = ["abca", "abcd", "abab", "xyzx", "xyzy", "wxyz"]
+            .iter()
+            .map(|line| parse_rucksack(line, 2, &alphabet))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let misplaced = validate_misplaced_items(&rucksacks, &alphabet, Validation::Lenient)?;
+        assert_eq!(misplaced.items.len(), 1 + 2 + 1 + 1);
+        assert_eq!(misplaced.warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(), vec![
+            "The rucksack on line 2 has no misplaced items, expected one.",
+            "The rucksack on line 3 has 2 misplaced items (a, b), expected one.",
+            "The rucksack on line 6 has no misplaced items, expected one.",
+        ]);
+
+        let error = validate_misplaced_items(&rucksacks, &alphabet, Validation::Strict).unwrap_err().to_string();
+        assert!(error.contains("rucksack on line 3 has 2 misplaced items (a, b)"));
+
+        let grouping = grouping::group_rucksacks(rucksacks.len(), 3, &GroupingMode::Consecutive)?;
+        let badges = validate_badges(&rucksacks, &grouping, &alphabet, Validation::Lenient)?;
+        assert_eq!(badges.items.iter().map(|item| item.get_symbol()).collect::<String>(), "abxyz");
+        assert_eq!(badges.warnings.iter().map(|warning| warning.to_string()).collect::<Vec<_>>(), vec![
+            "The group on lines 1, 2, 3 has 2 badges (a, b), expected one.",
+            "The group on lines 4, 5, 6 has 3 badges (x, y, z), expected one.",
+        ]);
+        assert!(validate_badges(&rucksacks, &grouping, &alphabet, Validation::Strict).is_err());
+
+        Ok(())
+    }
+}
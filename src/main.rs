@@ -1,15 +1,62 @@
 use anyhow::Ok;
 
+pub mod aoc1;
+pub mod aoc2;
+pub mod aoc3;
 mod aoc6;
 
 fn main() -> Result<(), anyhow::Error>
 {
     let input = std::fs::read_to_string("input.txt")?;
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
     
     println!("Input:\n{input}");
-    let output = aoc6::run(input)?;
+    let output = run(&args.iter().map(String::as_str).collect::<Vec<_>>(), input)?;
     println!("Output:\n{output}");
     std::fs::write("output.txt", output)?;
 
     Ok(())
 }
+
+// Picks the puzzle from the arguments, e.g. `cargo run -- aoc2`. Without any, runs the latest day.
+fn run(args: &[&str], input: String) -> Result<String, anyhow::Error>
+{
+    match args
+    {
+        [] => aoc6::run(input),
+        ["aoc1"] => aoc1::run(input),
+        ["aoc2", args @ ..] => run_aoc2(args, input),
+        ["aoc3"] => aoc3::run(input),
+        ["aoc3", "lenient"] => aoc3::run_lenient(input),
+        ["aoc3", "group-ids"] => aoc3::run_with_group_ids(input),
+        ["aoc3", "infer-groups"] => aoc3::run_inferred_groups(input),
+        ["aoc3", "inventory"] => aoc3::run_inventory(input),
+        ["aoc3", "bulk"] => aoc3::run_bulk(input),
+        _ => Err(anyhow::Error::msg(format!("'{}' is not a known puzzle.", args.join(" ")))),
+    }
+}
+
+// `aoc2 [mode] [--scoring <path>]`, scored by the 2022 rules unless a scoring file is given.
+fn run_aoc2(args: &[&str], input: String) -> Result<String, anyhow::Error>
+{
+    let (args, scoring_path) = match args
+    {
+        [args @ .., "--scoring", path] => (args, Some(*path)),
+        _ => (args, None),
+    };
+
+    match args
+    {
+        [] => aoc2::run(input, scoring_path),
+        ["decrypt"] => aoc2::run_decrypt(input, scoring_path),
+        ["simulate"] => aoc2::run_simulation(input, scoring_path),
+        ["equilibrium"] => aoc2::run_equilibrium(input, scoring_path),
+        ["synthesize"] => aoc2::run_synthesis(input, scoring_path),
+        ["report"] => aoc2::run_report(input, scoring_path),
+        ["bulk"] => aoc2::run_bulk(input, scoring_path),
+        ["series"] => aoc2::run_series(input, scoring_path),
+        ["diff"] => aoc2::run_diff(input, scoring_path),
+        ["diff-interpretations"] => aoc2::run_interpretation_diff(input, scoring_path),
+        _ => Err(anyhow::Error::msg(format!("'aoc2 {}' is not a known puzzle.", args.join(" ")))),
+    }
+}